@@ -0,0 +1,165 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (data (i32.const 0) "\00\00\00\00\00\00\00\00\01\00\00\00\00\00\00\00\00\00\00\00\00\00\00\80\ff\ff\ff\ff\00\00\00\00\00\00\00\00\ff\ff\ff\ff\ef\cd\ab\89\67\45\23\01\ff\ff\ff\ff\ff\ff\ff\ff\00\01\00\00\00\00\00\00")
+ (func $main
+	(i32.clz (i32.const 0))
+	(i32.const 32)
+	call $assert_eq
+	(i32.clz (i32.const 1))
+	(i32.const 31)
+	call $assert_eq
+	(i32.clz (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.clz (i32.const -2147483648))
+	(i32.const 0)
+	call $assert_eq
+	(i32.clz (i32.const 15728640))
+	(i32.const 8)
+	call $assert_eq
+	(i32.clz (i32.const 305419896))
+	(i32.const 3)
+	call $assert_eq
+	(i32.clz (i32.const 256))
+	(i32.const 23)
+	call $assert_eq
+	(i32.ctz (i32.const 0))
+	(i32.const 32)
+	call $assert_eq
+	(i32.ctz (i32.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ctz (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ctz (i32.const -2147483648))
+	(i32.const 31)
+	call $assert_eq
+	(i32.ctz (i32.const 15728640))
+	(i32.const 20)
+	call $assert_eq
+	(i32.ctz (i32.const 305419896))
+	(i32.const 3)
+	call $assert_eq
+	(i32.ctz (i32.const 256))
+	(i32.const 8)
+	call $assert_eq
+	(i32.popcnt (i32.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i32.popcnt (i32.const 1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.popcnt (i32.const -1))
+	(i32.const 32)
+	call $assert_eq
+	(i32.popcnt (i32.const -2147483648))
+	(i32.const 1)
+	call $assert_eq
+	(i32.popcnt (i32.const 15728640))
+	(i32.const 4)
+	call $assert_eq
+	(i32.popcnt (i32.const 305419896))
+	(i32.const 13)
+	call $assert_eq
+	(i32.popcnt (i32.const 256))
+	(i32.const 1)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 0))))
+	(i32.load (i32.const 1024))
+	(i32.const 64)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 8))))
+	(i32.load (i32.const 1024))
+	(i32.const 63)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 16))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 24))))
+	(i32.load (i32.const 1024))
+	(i32.const 32)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 32))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 40))))
+	(i32.load (i32.const 1024))
+	(i32.const 7)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 48))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.clz (i64.load (i32.const 56))))
+	(i32.load (i32.const 1024))
+	(i32.const 55)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 0))))
+	(i32.load (i32.const 1024))
+	(i32.const 64)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 8))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 16))))
+	(i32.load (i32.const 1024))
+	(i32.const 63)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 24))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 32))))
+	(i32.load (i32.const 1024))
+	(i32.const 32)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 40))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 48))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.ctz (i64.load (i32.const 56))))
+	(i32.load (i32.const 1024))
+	(i32.const 8)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 0))))
+	(i32.load (i32.const 1024))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 8))))
+	(i32.load (i32.const 1024))
+	(i32.const 1)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 16))))
+	(i32.load (i32.const 1024))
+	(i32.const 1)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 24))))
+	(i32.load (i32.const 1024))
+	(i32.const 32)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 32))))
+	(i32.load (i32.const 1024))
+	(i32.const 32)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 40))))
+	(i32.load (i32.const 1024))
+	(i32.const 32)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 48))))
+	(i32.load (i32.const 1024))
+	(i32.const 64)
+	call $assert_eq
+	(i64.store (i32.const 1024) (i64.popcnt (i64.load (i32.const 56))))
+	(i32.load (i32.const 1024))
+	(i32.const 1)
+	call $assert_eq)
+ (start $main))
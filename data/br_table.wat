@@ -0,0 +1,69 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ ;; Small table, lowered to a chain of comparisons.
+ (func $small (param $x i32) (result i32)
+	(block $out (result i32)
+	 (block $b
+		(block $a
+		 ;; Extra value that the branch has to drop.
+		 (i32.const 99)
+		 (br_table $a $b (local.get $x)))
+		(br_table $out (i32.const 10) (i32.const 0)))
+	 (br_table $out (i32.const 20) (i32.const 0))))
+ ;; Large table, lowered to a computed jump.
+ (func $large (param $x i32) (result i32)
+	(block $out (result i32)
+	 (block $d
+		(block $c
+		 (block $b
+			(block $a
+			 (i32.const 99)
+			 (br_table $a $b $c $b $a $d (local.get $x)))
+			(br_table $out (i32.const 10) (i32.const 0)))
+		 (br_table $out (i32.const 20) (i32.const 0)))
+		(br_table $out (i32.const 30) (i32.const 0)))
+	 (i32.const 40)))
+ ;; Branch carrying a value out of the function.
+ (func $value (param $x i32) (result i32)
+	(i32.const 1)
+	(i32.const 2)
+	(i32.const 3)
+	(br_table 0 (local.get $x)))
+ (func $main
+	(call $small (i32.const 0))
+	(i32.const 10)
+	call $assert_eq
+	(call $small (i32.const 1))
+	(i32.const 20)
+	call $assert_eq
+	(call $small (i32.const 2))
+	(i32.const 20)
+	call $assert_eq
+	(call $small (i32.const -1))
+	(i32.const 20)
+	call $assert_eq
+	(call $large (i32.const 0))
+	(i32.const 10)
+	call $assert_eq
+	(call $large (i32.const 1))
+	(i32.const 20)
+	call $assert_eq
+	(call $large (i32.const 2))
+	(i32.const 30)
+	call $assert_eq
+	(call $large (i32.const 3))
+	(i32.const 20)
+	call $assert_eq
+	(call $large (i32.const 4))
+	(i32.const 10)
+	call $assert_eq
+	(call $large (i32.const 5))
+	(i32.const 40)
+	call $assert_eq
+	(call $large (i32.const 100))
+	(i32.const 40)
+	call $assert_eq
+	(call $value (i32.const 7))
+	(i32.const 3)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,103 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ ;; Appends a digit to the trace of the blocks that ran.
+ (func $visit (param $trace i32) (param $digit i32) (result i32)
+	(local $twice i32)
+	(local.set $twice (i32.add (local.get $trace) (local.get $trace)))
+	(i32.add
+	 (local.get $digit)
+	 (i32.add
+		(i32.add
+		 (i32.add (local.get $twice) (local.get $twice))
+		 (i32.add (local.get $twice) (local.get $twice)))
+		(local.get $twice))))
+ ;; Two sibling blocks followed by a branch out of a third one.
+ (func $siblings (result i32)
+	(local $trace i32)
+	(block (local.set $trace (call $visit (local.get $trace) (i32.const 1))))
+	(block (local.set $trace (call $visit (local.get $trace) (i32.const 2))))
+	(block
+		(br 0)
+		(local.set $trace (call $visit (local.get $trace) (i32.const 9))))
+	(call $visit (local.get $trace) (i32.const 3)))
+ ;; Branches to every level of a nest that follows sibling blocks.
+ (func $nested (param $depth i32) (result i32)
+	(local $trace i32)
+	(block (local.set $trace (call $visit (local.get $trace) (i32.const 1))))
+	(block $a
+		(block $b
+			(block $c
+				(br_if $c (i32.eq (local.get $depth) (i32.const 0)))
+				(br_if $b (i32.eq (local.get $depth) (i32.const 1)))
+				(br $a))
+			(local.set $trace (call $visit (local.get $trace) (i32.const 2))))
+		(local.set $trace (call $visit (local.get $trace) (i32.const 3))))
+	(call $visit (local.get $trace) (i32.const 4)))
+ ;; Loops next to each other and inside of each other.
+ (func $loops (result i32)
+	(local $trace i32)
+	(local $i i32)
+	(local $j i32)
+	(loop $first
+		(local.set $i (i32.add (local.get $i) (i32.const 1)))
+		(br_if $first (i32.eq (local.get $i) (i32.const 1))))
+	(local.set $trace (call $visit (local.get $trace) (local.get $i)))
+	(local.set $i (i32.const 0))
+	(block $done
+		(loop $outer
+			(br_if $done (i32.eq (local.get $i) (i32.const 2)))
+			(local.set $j (i32.const 0))
+			(loop $inner
+				(local.set $trace
+				 (call $visit (local.get $trace) (i32.add (local.get $i) (i32.const 5))))
+				(local.set $j (i32.add (local.get $j) (i32.const 1)))
+				(br_if $inner (i32.eq (local.get $j) (i32.const 1))))
+			(local.set $i (i32.add (local.get $i) (i32.const 1)))
+			(br $outer)))
+	(local.get $trace))
+ ;; Branches out of ifs, with and without else arms.
+ (func $ifs (param $x i32) (result i32)
+	(local $trace i32)
+	(if (local.get $x)
+	 (then (local.set $trace (call $visit (local.get $trace) (i32.const 1)))))
+	(block $out
+		(if (i32.eq (local.get $x) (i32.const 0))
+		 (then
+			(local.set $trace (call $visit (local.get $trace) (i32.const 2)))
+			(br $out))
+		 (else
+			(if (i32.eq (local.get $x) (i32.const 2))
+			 (then (br 2)))
+			(local.set $trace (call $visit (local.get $trace) (i32.const 3)))
+			(br 0)))
+		(local.set $trace (call $visit (local.get $trace) (i32.const 4))))
+	(call $visit (local.get $trace) (i32.const 5)))
+ (func $main
+	(call $siblings)
+	(i32.const 123)
+	call $assert_eq
+	(call $nested (i32.const 0))
+	(i32.const 1234)
+	call $assert_eq
+	(call $nested (i32.const 1))
+	(i32.const 134)
+	call $assert_eq
+	(call $nested (i32.const 2))
+	(i32.const 14)
+	call $assert_eq
+	(call $nested (i32.const 7))
+	(i32.const 14)
+	call $assert_eq
+	(call $loops)
+	(i32.const 25566)
+	call $assert_eq
+	(call $ifs (i32.const 0))
+	(i32.const 25)
+	call $assert_eq
+	(call $ifs (i32.const 1))
+	(i32.const 1345)
+	call $assert_eq
+	(call $ifs (i32.const 2))
+	(i32.const 15)
+	call $assert_eq)
+ (start $main))
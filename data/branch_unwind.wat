@@ -0,0 +1,84 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ ;; Leaves extra values on the stack when breaking out of nested blocks.
+ (func $nested (param $x i32) (result i32)
+	(local $y i32)
+	(local.set $y (i32.const 100))
+	(block $outer (result i32)
+		(i32.const 1)
+		(i32.const 2)
+		(block $inner (result i32)
+			(i32.const 3)
+			(i32.const 4)
+			(local.get $x)
+			(br_if $outer (i32.eq (local.get $x) (i32.const 0)))
+			(br $inner))
+		(i32.add)
+		(i32.add))
+	;; Locals are still found after the branches.
+	(local.get $y)
+	(i32.add))
+ ;; A branch to the function block returns from the function.
+ (func $early (param $x i32) (result i32)
+	(i32.const 7)
+	(i32.const 8)
+	(local.get $x)
+	(br_if 0 (local.get $x))
+	(i32.add)
+	(i32.add))
+ ;; Carries two values to a loop while dropping the rest.
+ (func $count (param $n i32) (result i32)
+	(local $steps i32)
+	(local.get $n)
+	(i32.const 0)
+	(loop $next (param i32 i32) (result i32)
+		(local.set $steps)
+		(local.set $n)
+		(i32.const 1000)
+		(local.get $n)
+		(i32.add (local.get $n) (i32.const -1))
+		(i32.add (local.get $steps) (i32.const 1))
+		(br_if $next (i32.xor (i32.eq (local.get $n) (i32.const 1)) (i32.const 1)))
+		;; Only the last iteration's values are left: 1000, 1, 0 and the steps.
+		(i32.add)
+		(i32.add)
+		(i32.add)))
+ (func $main
+	(local $z i32)
+	(local.set $z (i32.const 50))
+	(call $nested (i32.const 0))
+	(i32.const 100)
+	call $assert_eq
+	(call $nested (i32.const 5))
+	(i32.const 108)
+	call $assert_eq
+	(local.get $z)
+	(i32.const 50)
+	call $assert_eq
+
+	(call $early (i32.const 3))
+	(i32.const 3)
+	call $assert_eq
+	(call $early (i32.const 0))
+	(i32.const 15)
+	call $assert_eq
+
+	(call $count (i32.const 6))
+	(i32.const 1007)
+	call $assert_eq
+
+	;; br_if that is not taken keeps the stack as it was.
+	(i32.const 11)
+	(block $b (result i32)
+		(i32.const 12)
+		(i32.const 13)
+		(br_if $b (i32.const 0))
+		(i32.add))
+	(i32.const 25)
+	call $assert_eq
+	(i32.const 11)
+	call $assert_eq
+	(local.get $z)
+	(i32.const 50)
+	call $assert_eq)
+ (start $main))
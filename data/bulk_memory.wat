@@ -0,0 +1,114 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (data $hello "hello, world")
+ (data $numbers "\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\10\11\12\13\14\15\16\17\18\19\1a\1b\1c\1d\1e\1f\20\21\22\23\24\25\26\27\28\29\2a\2b\2c\2d\2e\2f")
+ (data (i32.const 1000) "active")
+ ;; Writes `len` bytes `seed + 7 * i` starting at `address`.
+ (func $pattern (param $address i32) (param $len i32) (param $seed i32)
+	(block $done
+	 (loop $next
+		(br_if $done (i32.eqz (local.get $len)))
+		(i32.store8 (local.get $address) (local.get $seed))
+		(local.set $address (i32.add (local.get $address) (i32.const 1)))
+		(local.set $seed (i32.add (local.get $seed) (i32.const 7)))
+		(local.set $len (i32.sub (local.get $len) (i32.const 1)))
+		(br $next))))
+ ;; Checks the bytes written by `$pattern`.
+ (func $check (param $address i32) (param $len i32) (param $seed i32)
+	(block $done
+	 (loop $next
+		(br_if $done (i32.eqz (local.get $len)))
+		(i32.load8_u (local.get $address))
+		(i32.and (local.get $seed) (i32.const 0xff))
+		call $assert_eq
+		(local.set $address (i32.add (local.get $address) (i32.const 1)))
+		(local.set $seed (i32.add (local.get $seed) (i32.const 7)))
+		(local.set $len (i32.sub (local.get $len) (i32.const 1)))
+		(br $next))))
+ (func $main
+	;; Fill an unaligned range that covers whole words, using the lowest byte.
+	(memory.fill (i32.const 5) (i32.const 0x1ab) (i32.const 95))
+	(i32.load8_u (i32.const 4))
+	(i32.const 0)
+	call $assert_eq
+	(i32.load (i32.const 5))
+	(i32.const 0xabababab)
+	call $assert_eq
+	(i32.load (i32.const 40))
+	(i32.const 0xabababab)
+	call $assert_eq
+	(i32.load (i32.const 96))
+	(i32.const 0xabababab)
+	call $assert_eq
+	(i32.load8_u (i32.const 100))
+	(i32.const 0)
+	call $assert_eq
+	(memory.fill (i32.const 5) (i32.const 0) (i32.const 95))
+
+	;; Aligned copy of whole words.
+	(call $pattern (i32.const 0) (i32.const 64) (i32.const 1))
+	(memory.copy (i32.const 128) (i32.const 0) (i32.const 64))
+	(call $check (i32.const 128) (i32.const 64) (i32.const 1))
+	(i32.load8_u (i32.const 192))
+	(i32.const 0)
+	call $assert_eq
+
+	;; Unaligned copy between non-overlapping ranges.
+	(memory.copy (i32.const 203) (i32.const 3) (i32.const 40))
+	(call $check (i32.const 203) (i32.const 40) (i32.const 22))
+
+	;; Overlapping copy to a higher address.
+	(call $pattern (i32.const 256) (i32.const 100) (i32.const 3))
+	(memory.copy (i32.const 261) (i32.const 256) (i32.const 100))
+	(call $check (i32.const 261) (i32.const 100) (i32.const 3))
+	(call $check (i32.const 256) (i32.const 5) (i32.const 3))
+
+	;; Overlapping copy to a lower address.
+	(call $pattern (i32.const 512) (i32.const 100) (i32.const 5))
+	(memory.copy (i32.const 505) (i32.const 512) (i32.const 100))
+	(call $check (i32.const 505) (i32.const 100) (i32.const 5))
+	(call $check (i32.const 605) (i32.const 7) (i32.const 656))
+
+	;; Overlapping aligned copies in both directions.
+	(call $pattern (i32.const 1024) (i32.const 96) (i32.const 9))
+	(memory.copy (i32.const 1056) (i32.const 1024) (i32.const 96))
+	(call $check (i32.const 1056) (i32.const 96) (i32.const 9))
+	(memory.copy (i32.const 1024) (i32.const 1056) (i32.const 96))
+	(call $check (i32.const 1024) (i32.const 96) (i32.const 9))
+
+	;; Initialize memory from passive segments.
+	(memory.init $hello (i32.const 2000) (i32.const 7) (i32.const 5))
+	(i32.load (i32.const 2000))
+	(i32.const 0x6c726f77)
+	call $assert_eq
+	(i32.load8_u (i32.const 2004))
+	(i32.const 0x64)
+	call $assert_eq
+	(memory.init $numbers (i32.const 2048) (i32.const 0) (i32.const 48))
+	(i32.load (i32.const 2048))
+	(i32.const 0x03020100)
+	call $assert_eq
+	(i32.load (i32.const 2092))
+	(i32.const 0x2f2e2d2c)
+	call $assert_eq
+	(memory.init $numbers (i32.const 2101) (i32.const 10) (i32.const 4))
+	(i32.load (i32.const 2101))
+	(i32.const 0x0d0c0b0a)
+	call $assert_eq
+
+	;; Dropped and active segments can still copy nothing.
+	(data.drop $hello)
+	(memory.init $hello (i32.const 0) (i32.const 0) (i32.const 0))
+	(memory.init 2 (i32.const 0) (i32.const 0) (i32.const 0))
+
+	;; Empty ranges may end at the end of the memory.
+	(memory.fill (i32.const 65536) (i32.const 1) (i32.const 0))
+	(memory.copy (i32.const 65536) (i32.const 0) (i32.const 0))
+	(memory.copy (i32.const 65535) (i32.const 0) (i32.const 1))
+	(i32.load8_u (i32.const 65535))
+	(i32.const 1)
+	call $assert_eq
+ )
+ (start $main)
+)
@@ -0,0 +1,60 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (type $unary (func (param i32) (result i32)))
+ (type $binary (func (param i32 i32) (result i32)))
+ ;; The same signature as `$unary`, which calls may use interchangeably.
+ (type $unary_again (func (param i32) (result i32)))
+ (type $pair (func (param i32) (result i32 i32)))
+ (table 8 funcref)
+ (elem (i32.const 0) $double $add $square $square $split)
+ ;; Overwrites the entries of `$square` and leaves the last entries null.
+ (elem (i32.const 2) func $factorial $sub)
+ (elem (i32.const 5) $double)
+ (func $double (type $unary) (i32.add (local.get 0) (local.get 0)))
+ (func $add (type $binary) (i32.add (local.get 0) (local.get 1)))
+ (func $square (type $unary) (i32.mul (local.get 0) (local.get 0)))
+ (func $sub (type $binary) (i32.sub (local.get 0) (local.get 1)))
+ (func $split (type $pair)
+	(i32.shr_u (local.get 0) (i32.const 16))
+	(i32.and (local.get 0) (i32.const 0xffff)))
+ ;; Recurses through the table.
+ (func $factorial (type $unary_again)
+	(if (result i32) (i32.eqz (local.get 0))
+	 (then (i32.const 1))
+	 (else
+		(i32.mul
+		 (local.get 0)
+		 (call_indirect (type $unary) (i32.sub (local.get 0) (i32.const 1)) (i32.const 2))))))
+ ;; Applies the binary function at `index` to the results of the unary one at `index - 1`.
+ (func $apply (param $index i32) (param $x i32) (result i32)
+	(call_indirect (type $unary) (local.get $x) (i32.sub (local.get $index) (i32.const 1)))
+	(local.get $x)
+	(call_indirect (type $binary) (local.get $index)))
+ (func $main
+	(call_indirect (type $unary) (i32.const 21) (i32.const 0))
+	(i32.const 42)
+	call $assert_eq
+	(call_indirect (type $binary) (i32.const 2) (i32.const 3) (i32.const 1))
+	(i32.const 5)
+	call $assert_eq
+	(call_indirect (type $unary_again) (i32.const 5) (i32.const 2))
+	(i32.const 120)
+	call $assert_eq
+	(call_indirect (type $binary) (i32.const 2) (i32.const 3) (i32.const 3))
+	(i32.const -1)
+	call $assert_eq
+	(call_indirect (type $pair) (i32.const 0x12345678) (i32.const 4))
+	(i32.const 0x5678)
+	call $assert_eq
+	(i32.const 0x1234)
+	call $assert_eq
+	(call_indirect (type $unary) (i32.const 8) (i32.const 5))
+	(i32.const 16)
+	call $assert_eq
+	(call $apply (i32.const 1) (i32.const 10))
+	(i32.const 30)
+	call $assert_eq
+	(call $apply (i32.const 3) (i32.const 4))
+	(i32.const 20)
+	call $assert_eq)
+ (start $main))
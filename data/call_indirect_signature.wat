@@ -0,0 +1,13 @@
+(module
+ (type $unary (func (param i32) (result i32)))
+ (table 2 funcref)
+ (elem (i32.const 0) $add $double)
+ (func $double (type $unary) (i32.add (local.get 0) (local.get 0)))
+ (func $add (param i32 i32) (result i32) (i32.add (local.get 0) (local.get 1)))
+ (func $call (param $index i32) (result i32)
+	;; Entry 0 holds a function that takes two parameters.
+	(call_indirect (type $unary) (i32.const 1) (local.get $index)))
+ (func $main
+	(call $call (i32.const 0))
+	drop)
+ (start $main))
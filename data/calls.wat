@@ -0,0 +1,28 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ ;; Sum of 1..n computed recursively.
+ (func $sum (param $n i32) (result i32)
+	(if (result i32)
+	 (local.get $n)
+	 (then
+		(i32.add
+		 (local.get $n)
+		 (call $sum (i32.add (local.get $n) (i32.const -1)))))
+	 (else (i32.const 0))))
+ (func $add3 (param $a i32) (param $b i32) (param $c i32) (result i32)
+	(local $tmp i32)
+	(local.set $tmp (i32.add (local.get $a) (local.get $b)))
+	(i32.add (local.get $tmp) (local.get $c)))
+ (func $main
+	(local $x i32)
+	(local.set $x (i32.const 100))
+	(call $add3 (local.get $x) (i32.const 10) (call $sum (i32.const 4)))
+	(i32.const 120)
+	call $assert_eq
+	(call $sum (i32.const 10))
+	(i32.const 55)
+	call $assert_eq
+	(local.get $x)
+	(i32.const 100)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,34 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (data (i32.const 0) "hello")
+ ;; Spans three words.
+ (data (i32.const 30) "\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\10\11\12\13\14\15\16\17\18\19\1a\1b\1c\1d\1e\1f\20\21\22\23\24\25\26\27\28")
+ ;; Overwrites part of the previous segment.
+ (data (i32.const 64) "\ff\fe")
+ (func $main
+	(i32.load8_u (i32.const 4))
+	(i32.const 0x6f)
+	call $assert_eq
+	(i32.load (i32.const 0))
+	(i32.const 0x6c6c6568)
+	call $assert_eq
+	(i32.load (i32.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i32.load (i32.const 30))
+	(i32.const 0x04030201)
+	call $assert_eq
+	(i32.load16_u (i32.const 62))
+	(i32.const 0x2221)
+	call $assert_eq
+	(i32.load16_u (i32.const 64))
+	(i32.const 0xfeff)
+	call $assert_eq
+	(i32.load (i32.const 66))
+	(i32.const 0x28272625)
+	call $assert_eq
+	(i32.load16_u (i32.const 69))
+	(i32.const 0x0028)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,76 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (global $counter (mut i32) (i32.const 0))
+ ;; Returns from inside of a loop nested in blocks, with extra values on the stack.
+ (func $find (param $target i32) (result i32)
+	(local $i i32)
+	(i32.const 77)
+	(block $out
+		(loop $next
+			(i32.const 88)
+			(if (i32.eq (local.get $i) (local.get $target))
+			 (then
+				(i32.const 99)
+				(i32.mul (local.get $i) (i32.const 10))
+				(return)))
+			(drop)
+			(local.set $i (i32.add (local.get $i) (i32.const 1)))
+			(br_if $out (i32.eq (local.get $i) (i32.const 5)))
+			(br $next)))
+	(drop)
+	(i32.const -1))
+ ;; Returns several values.
+ (func $divmod (param $a i32) (param $b i32) (result i32 i32)
+	(if (i32.eqz (local.get $b))
+	 (then
+		(i32.const 0)
+		(i32.const 0)
+		(return)))
+	(i32.div_u (local.get $a) (local.get $b))
+	(i32.rem_u (local.get $a) (local.get $b)))
+ ;; Returns without results.
+ (func $bump (param $x i32)
+	(block
+		(br_if 0 (local.get $x))
+		(return))
+	(global.set $counter (i32.add (global.get $counter) (local.get $x))))
+ ;; Returns through `br_table` and from a recursive call.
+ (func $gcd (param $a i32) (param $b i32) (result i32)
+	(block (result i32)
+		(i32.const 5)
+		(local.get $a)
+		(br_table 1 0 (local.get $b)))
+	(drop)
+	(call $gcd (local.get $b) (i32.rem_u (local.get $a) (local.get $b)))
+	(return)
+	(unreachable))
+ (func $main
+	(call $find (i32.const 3))
+	(i32.const 30)
+	call $assert_eq
+	(call $find (i32.const 9))
+	(i32.const -1)
+	call $assert_eq
+
+	(call $divmod (i32.const 17) (i32.const 5))
+	(i32.const 2)
+	call $assert_eq
+	(i32.const 3)
+	call $assert_eq
+	(call $divmod (i32.const 17) (i32.const 0))
+	(i32.add)
+	(i32.const 0)
+	call $assert_eq
+
+	(call $bump (i32.const 0))
+	(call $bump (i32.const 4))
+	(global.get $counter)
+	(i32.const 4)
+	call $assert_eq
+
+	(call $gcd (i32.const 84) (i32.const 36))
+	(i32.const 12)
+	call $assert_eq
+	(return)
+	(call $assert_eq (i32.const 0) (i32.const 1)))
+ (start $main))
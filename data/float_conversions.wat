@@ -0,0 +1,160 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (func $assert_eq64 (param $lhs i64) (param $rhs i64)
+	(i32.wrap_i64 (local.get $lhs))
+	(i32.wrap_i64 (local.get $rhs))
+	call $assert_eq
+	(i32.wrap_i64 (i64.shr_u (local.get $lhs) (i64.const 32)))
+	(i32.wrap_i64 (i64.shr_u (local.get $rhs) (i64.const 32)))
+	call $assert_eq)
+ (func $check32 (param $value f32) (param $bits i32)
+	(f32.store (i32.const 0) (local.get $value))
+	(i32.load (i32.const 0))
+	local.get $bits
+	call $assert_eq)
+ (func $check64 (param $value f64) (param $bits i64)
+	(f64.store (i32.const 0) (local.get $value))
+	(i64.load (i32.const 0))
+	local.get $bits
+	call $assert_eq64)
+ (func $main
+	(i32.trunc_f32_s (f32.const -2.9))
+	(i32.const 0xfffffffe)
+	call $assert_eq
+	(i32.trunc_f32_s (f32.const -0x1p31))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_f32_s (f32.const 0x1.fffffep30))
+	(i32.const 0x7fffff80)
+	call $assert_eq
+	(i32.trunc_f32_u (f32.const -0.9))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_f32_u (f32.const 0x1.fffffep31))
+	(i32.const 0xffffff00)
+	call $assert_eq
+	(i32.trunc_f64_s (f64.const -2147483648.9))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_f64_s (f64.const 2147483647.9))
+	(i32.const 0x7fffffff)
+	call $assert_eq
+	(i32.trunc_f64_s (f64.const -0))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_f64_u (f64.const 4294967295.9))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_f64_u (f64.const 0x1p-1074))
+	(i32.const 0x0)
+	call $assert_eq
+	(i64.trunc_f32_s (f32.const -0x1p63))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_f32_s (f32.const 123456.7))
+	(i64.const 0x1e240)
+	call $assert_eq64
+	(i64.trunc_f32_u (f32.const 0x1.fffffep63))
+	(i64.const 0xffffff0000000000)
+	call $assert_eq64
+	(i64.trunc_f64_s (f64.const -0x1p63))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_f64_s (f64.const 0x1.fffffffffffffp62))
+	(i64.const 0x7ffffffffffffc00)
+	call $assert_eq64
+	(i64.trunc_f64_s (f64.const -1e18))
+	(i64.const 0xf21f494c589c0000)
+	call $assert_eq64
+	(i64.trunc_f64_u (f64.const 0x1.fffffffffffffp63))
+	(i64.const 0xfffffffffffff800)
+	call $assert_eq64
+	(i64.trunc_f64_u (f64.const -0.5))
+	(i64.const 0x0)
+	call $assert_eq64
+	(f32.convert_i32_s (i32.const -1))
+	(i32.const 0xbf800000)
+	call $check32
+	(f32.convert_i32_s (i32.const 16777217))
+	(i32.const 0x4b800000)
+	call $check32
+	(f32.convert_i32_s (i32.const -2147483648))
+	(i32.const 0xcf000000)
+	call $check32
+	(f32.convert_i32_u (i32.const 0xffffffff))
+	(i32.const 0x4f800000)
+	call $check32
+	(f32.convert_i32_u (i32.const 0))
+	(i32.const 0x0)
+	call $check32
+	(f32.convert_i64_s (i64.const -9223372036854775808))
+	(i32.const 0xdf000000)
+	call $check32
+	(f32.convert_i64_s (i64.const 0x0020000020000001))
+	(i32.const 0x5a000001)
+	call $check32
+	(f32.convert_i64_u (i64.const 0xffffffffffffffff))
+	(i32.const 0x5f800000)
+	call $check32
+	(f32.convert_i64_u (i64.const 0x8000008000000000))
+	(i32.const 0x5f000000)
+	call $check32
+	(f64.convert_i32_s (i32.const -2147483648))
+	(i64.const 0xc1e0000000000000)
+	call $check64
+	(f64.convert_i32_u (i32.const 0xffffffff))
+	(i64.const 0x41efffffffe00000)
+	call $check64
+	(f64.convert_i64_s (i64.const -9223372036854775808))
+	(i64.const 0xc3e0000000000000)
+	call $check64
+	(f64.convert_i64_s (i64.const 9007199254740993))
+	(i64.const 0x4340000000000000)
+	call $check64
+	(f64.convert_i64_u (i64.const 0xffffffffffffffff))
+	(i64.const 0x43f0000000000000)
+	call $check64
+	(f64.convert_i64_u (i64.const 0x8000000000000c00))
+	(i64.const 0x43e0000000000002)
+	call $check64
+	(f64.convert_i64_u (i64.const 0x8000000000000401))
+	(i64.const 0x43e0000000000001)
+	call $check64
+	(f32.demote_f64 (f64.const 0.1))
+	(i32.const 0x3dcccccd)
+	call $check32
+	(f32.demote_f64 (f64.const 0x1.fffffffp127))
+	(i32.const 0x7f800000)
+	call $check32
+	(f32.demote_f64 (f64.const 0x1p-150))
+	(i32.const 0x0)
+	call $check32
+	(f32.demote_f64 (f64.const 0x1.0000000000001p-150))
+	(i32.const 0x1)
+	call $check32
+	(f32.demote_f64 (f64.const -nan:0x1234567))
+	(i32.const 0xffc00000)
+	call $check32
+	(f64.promote_f32 (f32.const 0x1p-149))
+	(i64.const 0x36a0000000000000)
+	call $check64
+	(f64.promote_f32 (f32.const -inf))
+	(i64.const 0xfff0000000000000)
+	call $check64
+	(f64.promote_f32 (f32.const nan:0x200))
+	(i64.const 0x7ff8004000000000)
+	call $check64
+	(i32.reinterpret_f32 (f32.const -1.5))
+	(i32.const 0xbfc00000)
+	call $assert_eq
+	(i64.reinterpret_f64 (f64.const -nan:0x5))
+	(i64.const 0xfff0000000000005)
+	call $assert_eq64
+	(f32.reinterpret_i32 (i32.const 0x7fa00000))
+	(i32.const 0x7fa00000)
+	call $check32
+	(f64.reinterpret_i64 (i64.const 0x8000000000000001))
+	(i64.const 0x8000000000000001)
+	call $check64)
+ (start $main))
@@ -0,0 +1,393 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (func $check32 (param $value f32) (param $bits i32)
+	(f32.store (i32.const 0) (local.get $value))
+	(i32.load (i32.const 0))
+	local.get $bits
+	call $assert_eq)
+ (func $check64 (param $value f64) (param $bits i64)
+	(f64.store (i32.const 0) (local.get $value))
+	(i32.load (i32.const 0))
+	(i32.wrap_i64 (local.get $bits))
+	call $assert_eq
+	(i32.load (i32.const 4))
+	(i32.wrap_i64 (i64.shr_u (local.get $bits) (i64.const 32)))
+	call $assert_eq)
+ (func $main
+	(f64.add (f64.const 0.1) (f64.const 0.2))
+	(i64.const 0x3fd3333333333334)
+	call $check64
+	(f64.add (f64.const 1) (f64.const -1))
+	(i64.const 0x0)
+	call $check64
+	(f64.add (f64.const -0) (f64.const -0))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.add (f64.const inf) (f64.const -inf))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.add (f64.const 0x1.fffffffffffffp1023) (f64.const 0x1.fffffffffffffp1023))
+	(i64.const 0x7ff0000000000000)
+	call $check64
+	(f64.add (f64.const -nan:0x4) (f64.const 1))
+	(i64.const 0xfff8000000000004)
+	call $check64
+	(f64.add (f64.const 0x1p-1074) (f64.const 0x1p-1074))
+	(i64.const 0x2)
+	call $check64
+	(f64.add (f64.const 1) (f64.const 0x1p-53))
+	(i64.const 0x3ff0000000000000)
+	call $check64
+	(f64.add (f64.const 1) (f64.const 0x1.0000000000001p-53))
+	(i64.const 0x3ff0000000000001)
+	call $check64
+	(f64.sub (f64.const 1) (f64.const 0.9))
+	(i64.const 0x3fb9999999999998)
+	call $check64
+	(f64.sub (f64.const 0) (f64.const 0))
+	(i64.const 0x0)
+	call $check64
+	(f64.sub (f64.const -0) (f64.const 0))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.sub (f64.const inf) (f64.const inf))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.sub (f64.const 0x1p-1022) (f64.const 0x1.0000000000001p-1022))
+	(i64.const 0x8000000000000001)
+	call $check64
+	(f64.sub (f64.const 0x1p60) (f64.const 1))
+	(i64.const 0x43b0000000000000)
+	call $check64
+	(f64.mul (f64.const 0.1) (f64.const 3))
+	(i64.const 0x3fd3333333333334)
+	call $check64
+	(f64.mul (f64.const -0) (f64.const 5))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.mul (f64.const inf) (f64.const 0))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.mul (f64.const 0x1p-1000) (f64.const 0x1p-70))
+	(i64.const 0x10)
+	call $check64
+	(f64.mul (f64.const 0x1p-1000) (f64.const 0x1.8p-75))
+	(i64.const 0x1)
+	call $check64
+	(f64.mul (f64.const 0x1.fffffffffffffp1023) (f64.const 2))
+	(i64.const 0x7ff0000000000000)
+	call $check64
+	(f64.mul (f64.const -3) (f64.const nan:0x1234))
+	(i64.const 0x7ff8000000001234)
+	call $check64
+	(f64.div (f64.const 1) (f64.const 3))
+	(i64.const 0x3fd5555555555555)
+	call $check64
+	(f64.div (f64.const -1) (f64.const 0))
+	(i64.const 0xfff0000000000000)
+	call $check64
+	(f64.div (f64.const 0) (f64.const 0))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.div (f64.const 1) (f64.const inf))
+	(i64.const 0x0)
+	call $check64
+	(f64.div (f64.const inf) (f64.const inf))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.div (f64.const 0x1p-1074) (f64.const 2))
+	(i64.const 0x0)
+	call $check64
+	(f64.div (f64.const 0x1.8p-1073) (f64.const 2))
+	(i64.const 0x2)
+	call $check64
+	(f64.div (f64.const -10) (f64.const 0x1p-1074))
+	(i64.const 0xfff0000000000000)
+	call $check64
+	(f64.div (f64.const 22) (f64.const 7))
+	(i64.const 0x4009249249249249)
+	call $check64
+	(f64.sqrt (f64.const 2))
+	(i64.const 0x3ff6a09e667f3bcd)
+	call $check64
+	(f64.sqrt (f64.const -0))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.sqrt (f64.const -1))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.sqrt (f64.const inf))
+	(i64.const 0x7ff0000000000000)
+	call $check64
+	(f64.sqrt (f64.const 0x1p-1074))
+	(i64.const 0x1e60000000000000)
+	call $check64
+	(f64.sqrt (f64.const 1e300))
+	(i64.const 0x5f138d352e5096af)
+	call $check64
+	(f64.min (f64.const -0) (f64.const 0))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.min (f64.const -1) (f64.const nan))
+	(i64.const 0x7ff8000000000000)
+	call $check64
+	(f64.min (f64.const 1) (f64.const 2))
+	(i64.const 0x3ff0000000000000)
+	call $check64
+	(f64.min (f64.const -inf) (f64.const 3))
+	(i64.const 0xfff0000000000000)
+	call $check64
+	(f64.max (f64.const -0) (f64.const 0))
+	(i64.const 0x0)
+	call $check64
+	(f64.max (f64.const -inf) (f64.const 3))
+	(i64.const 0x4008000000000000)
+	call $check64
+	(f64.max (f64.const nan:0x8000000000005) (f64.const 1))
+	(i64.const 0x7ff8000000000005)
+	call $check64
+	(f64.ceil (f64.const -0.5))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.ceil (f64.const 1.2))
+	(i64.const 0x4000000000000000)
+	call $check64
+	(f64.ceil (f64.const -inf))
+	(i64.const 0xfff0000000000000)
+	call $check64
+	(f64.floor (f64.const -1.5))
+	(i64.const 0xc000000000000000)
+	call $check64
+	(f64.floor (f64.const 0.5))
+	(i64.const 0x0)
+	call $check64
+	(f64.floor (f64.const -0x1p-1074))
+	(i64.const 0xbff0000000000000)
+	call $check64
+	(f64.trunc (f64.const -2.7))
+	(i64.const 0xc000000000000000)
+	call $check64
+	(f64.trunc (f64.const 1e300))
+	(i64.const 0x7e37e43c8800759c)
+	call $check64
+	(f64.trunc (f64.const nan:0x1))
+	(i64.const 0x7ff0000000000001)
+	call $check64
+	(f64.nearest (f64.const 2.5))
+	(i64.const 0x4000000000000000)
+	call $check64
+	(f64.nearest (f64.const 3.5))
+	(i64.const 0x4010000000000000)
+	call $check64
+	(f64.nearest (f64.const -0.5))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.nearest (f64.const 0.5000000000000001))
+	(i64.const 0x3ff0000000000000)
+	call $check64
+	(f64.nearest (f64.const 0x1.fffffffffffffp51))
+	(i64.const 0x4330000000000000)
+	call $check64
+	(f64.abs (f64.const -nan))
+	(i64.const 0x7ff8000000000000)
+	call $check64
+	(f64.abs (f64.const -1.5))
+	(i64.const 0x3ff8000000000000)
+	call $check64
+	(f64.neg (f64.const 0))
+	(i64.const 0x8000000000000000)
+	call $check64
+	(f64.neg (f64.const nan))
+	(i64.const 0xfff8000000000000)
+	call $check64
+	(f64.copysign (f64.const 1) (f64.const -0))
+	(i64.const 0xbff0000000000000)
+	call $check64
+	(f64.copysign (f64.const -inf) (f64.const 1))
+	(i64.const 0x7ff0000000000000)
+	call $check64
+	(f64.eq (f64.const 0) (f64.const -0))
+	(i32.const 1)
+	call $assert_eq
+	(f64.eq (f64.const nan) (f64.const nan))
+	(i32.const 0)
+	call $assert_eq
+	(f64.ne (f64.const nan) (f64.const nan))
+	(i32.const 1)
+	call $assert_eq
+	(f64.ne (f64.const 1) (f64.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(f64.lt (f64.const -0) (f64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(f64.lt (f64.const -2) (f64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(f64.lt (f64.const 1) (f64.const nan))
+	(i32.const 0)
+	call $assert_eq
+	(f64.gt (f64.const 2) (f64.const 1))
+	(i32.const 1)
+	call $assert_eq
+	(f64.gt (f64.const -inf) (f64.const -0x1p-1074))
+	(i32.const 0)
+	call $assert_eq
+	(f64.le (f64.const nan) (f64.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(f64.le (f64.const 0) (f64.const -0))
+	(i32.const 1)
+	call $assert_eq
+	(f64.ge (f64.const -inf) (f64.const -inf))
+	(i32.const 1)
+	call $assert_eq
+	(f64.ge (f64.const -1) (f64.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(f32.add (f32.const 0.1) (f32.const 0.2))
+	(i32.const 0x3e99999a)
+	call $check32
+	(f32.add (f32.const 1) (f32.const -1))
+	(i32.const 0x0)
+	call $check32
+	(f32.add (f32.const 0x1.fffffep127) (f32.const 0x1p104))
+	(i32.const 0x7f800000)
+	call $check32
+	(f32.add (f32.const 1) (f32.const 0x1p-24))
+	(i32.const 0x3f800000)
+	call $check32
+	(f32.add (f32.const 1) (f32.const 0x1.000002p-24))
+	(i32.const 0x3f800001)
+	call $check32
+	(f32.add (f32.const 0x1p-149) (f32.const 0x1p-149))
+	(i32.const 0x2)
+	call $check32
+	(f32.sub (f32.const 1) (f32.const 0.9))
+	(i32.const 0x3dccccd0)
+	call $check32
+	(f32.sub (f32.const -0) (f32.const 0))
+	(i32.const 0x80000000)
+	call $check32
+	(f32.sub (f32.const 0x1p-126) (f32.const 0x1.000002p-126))
+	(i32.const 0x80000001)
+	call $check32
+	(f32.mul (f32.const 0.1) (f32.const 3))
+	(i32.const 0x3e99999a)
+	call $check32
+	(f32.mul (f32.const 0x1p-100) (f32.const 0x1p-49))
+	(i32.const 0x1)
+	call $check32
+	(f32.mul (f32.const 0x1p-100) (f32.const 0x1.8p-50))
+	(i32.const 0x1)
+	call $check32
+	(f32.mul (f32.const 0x1.fffffep127) (f32.const 2))
+	(i32.const 0x7f800000)
+	call $check32
+	(f32.mul (f32.const -0) (f32.const inf))
+	(i32.const 0xffc00000)
+	call $check32
+	(f32.div (f32.const 1) (f32.const 3))
+	(i32.const 0x3eaaaaab)
+	call $check32
+	(f32.div (f32.const -1) (f32.const -0))
+	(i32.const 0x7f800000)
+	call $check32
+	(f32.div (f32.const 0x1p-149) (f32.const 2))
+	(i32.const 0x0)
+	call $check32
+	(f32.div (f32.const 0x1.8p-148) (f32.const 2))
+	(i32.const 0x2)
+	call $check32
+	(f32.div (f32.const 22) (f32.const 7))
+	(i32.const 0x40492492)
+	call $check32
+	(f32.sqrt (f32.const 2))
+	(i32.const 0x3fb504f3)
+	call $check32
+	(f32.sqrt (f32.const 4))
+	(i32.const 0x40000000)
+	call $check32
+	(f32.sqrt (f32.const -0x1p-149))
+	(i32.const 0xffc00000)
+	call $check32
+	(f32.sqrt (f32.const 0x1p-149))
+	(i32.const 0x1a3504f3)
+	call $check32
+	(f32.min (f32.const -0) (f32.const 0))
+	(i32.const 0x80000000)
+	call $check32
+	(f32.min (f32.const nan:0x600000) (f32.const 1))
+	(i32.const 0x7fe00000)
+	call $check32
+	(f32.min (f32.const -1) (f32.const 1))
+	(i32.const 0xbf800000)
+	call $check32
+	(f32.max (f32.const -0) (f32.const 0))
+	(i32.const 0x0)
+	call $check32
+	(f32.max (f32.const -1) (f32.const 1))
+	(i32.const 0x3f800000)
+	call $check32
+	(f32.max (f32.const 1) (f32.const -nan))
+	(i32.const 0xffc00000)
+	call $check32
+	(f32.ceil (f32.const -0.5))
+	(i32.const 0x80000000)
+	call $check32
+	(f32.ceil (f32.const 1.2))
+	(i32.const 0x40000000)
+	call $check32
+	(f32.floor (f32.const -1.5))
+	(i32.const 0xc0000000)
+	call $check32
+	(f32.trunc (f32.const -2.7))
+	(i32.const 0xc0000000)
+	call $check32
+	(f32.trunc (f32.const nan:0x1))
+	(i32.const 0x7f800001)
+	call $check32
+	(f32.nearest (f32.const 2.5))
+	(i32.const 0x40000000)
+	call $check32
+	(f32.nearest (f32.const -3.5))
+	(i32.const 0xc0800000)
+	call $check32
+	(f32.nearest (f32.const 0x1.fffffep22))
+	(i32.const 0x4b000000)
+	call $check32
+	(f32.nearest (f32.const nan:0x1))
+	(i32.const 0x7fc00001)
+	call $check32
+	(f32.abs (f32.const -nan))
+	(i32.const 0x7fc00000)
+	call $check32
+	(f32.neg (f32.const 0))
+	(i32.const 0x80000000)
+	call $check32
+	(f32.copysign (f32.const 1) (f32.const -0))
+	(i32.const 0xbf800000)
+	call $check32
+	(f32.copysign (f32.const -inf) (f32.const 1))
+	(i32.const 0x7f800000)
+	call $check32
+	(f32.eq (f32.const 0) (f32.const -0))
+	(i32.const 1)
+	call $assert_eq
+	(f32.ne (f32.const nan) (f32.const nan))
+	(i32.const 1)
+	call $assert_eq
+	(f32.lt (f32.const -2) (f32.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(f32.gt (f32.const 2) (f32.const nan))
+	(i32.const 0)
+	call $assert_eq
+	(f32.le (f32.const -0) (f32.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(f32.ge (f32.const 1) (f32.const 2))
+	(i32.const 0)
+	call $assert_eq)
+ (start $main))
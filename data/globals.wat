@@ -0,0 +1,25 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (global $__stack_pointer (mut i32) (i32.const 1024))
+ (global $answer i32 (i32.const 42))
+ (global $counter (mut i32) (i32.const 0))
+ (func $push (param $size i32) (result i32)
+	(global.set $__stack_pointer
+	 (i32.add (global.get $__stack_pointer) (local.get $size)))
+	(global.get $__stack_pointer))
+ (func $main
+	(global.get $answer)
+	(i32.const 42)
+	call $assert_eq
+	(call $push (i32.const 16))
+	(i32.const 1040)
+	call $assert_eq
+	(call $push (i32.const 8))
+	(i32.const 1048)
+	call $assert_eq
+	(global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+	(global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+	(global.get $counter)
+	(i32.const 2)
+	call $assert_eq)
+ (start $main))
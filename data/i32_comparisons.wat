@@ -0,0 +1,82 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $main
+	(i32.eqz (i32.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i32.eqz (i32.const 7))
+	(i32.const 0)
+	call $assert_eq
+	(i32.eqz (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ne (i32.const 3) (i32.const 3))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ne (i32.const 3) (i32.const -3))
+	(i32.const 1)
+	call $assert_eq
+	(i32.gt_s (i32.const -1) (i32.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i32.gt_s (i32.const 0) (i32.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.gt_s (i32.const 5) (i32.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i32.gt_u (i32.const -1) (i32.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i32.gt_u (i32.const 0) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.gt_u (i32.const 5) (i32.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i32.le_s (i32.const -1) (i32.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i32.le_s (i32.const 0) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.le_s (i32.const 5) (i32.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i32.le_u (i32.const -1) (i32.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i32.le_u (i32.const 0) (i32.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.le_u (i32.const 5) (i32.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i32.ge_s (i32.const -2147483648) (i32.const 2147483647))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ge_s (i32.const 2147483647) (i32.const -2147483648))
+	(i32.const 1)
+	call $assert_eq
+	(i32.ge_s (i32.const 5) (i32.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i32.ge_u (i32.const -2147483648) (i32.const 2147483647))
+	(i32.const 1)
+	call $assert_eq
+	(i32.ge_u (i32.const 2147483647) (i32.const -2147483648))
+	(i32.const 0)
+	call $assert_eq
+	(i32.ge_u (i32.const 5) (i32.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i32.lt_s (i32.const -5) (i32.const -3))
+	(i32.const 1)
+	call $assert_eq
+	(i32.lt_u (i32.const -5) (i32.const -3))
+	(i32.const 1)
+	call $assert_eq
+	(i32.lt_u (i32.const -3) (i32.const -5))
+	(i32.const 0)
+	call $assert_eq)
+ (start $main))
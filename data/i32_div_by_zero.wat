@@ -0,0 +1,7 @@
+(module
+ (func $divide (param $a i32) (param $b i32) (result i32)
+	(i32.div_u (local.get $a) (local.get $b)))
+ (func $main
+	(call $divide (i32.const 7) (i32.const 0))
+	drop)
+ (start $main))
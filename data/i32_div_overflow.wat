@@ -0,0 +1,8 @@
+(module
+ (func $divide (param $a i32) (param $b i32) (result i32)
+	(i32.div_s (local.get $a) (local.get $b)))
+ (func $main
+	;; The quotient 2^31 does not fit into an i32.
+	(call $divide (i32.const 0x80000000) (i32.const -1))
+	drop)
+ (start $main))
@@ -0,0 +1,70 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $main
+	(i32.mul (i32.const 6) (i32.const 7))
+	(i32.const 42)
+	call $assert_eq
+	(i32.mul (i32.const -3) (i32.const 5))
+	(i32.const -15)
+	call $assert_eq
+	(i32.mul (i32.const 0x10000) (i32.const 0x10000))
+	(i32.const 0)
+	call $assert_eq
+	(i32.mul (i32.const -1) (i32.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.mul (i32.const 0x7FFFFFFF) (i32.const 3))
+	(i32.const 2147483645)
+	call $assert_eq
+	(i32.div_u (i32.const 17) (i32.const 5))
+	(i32.const 3)
+	call $assert_eq
+	(i32.div_u (i32.const -1) (i32.const 2))
+	(i32.const 2147483647)
+	call $assert_eq
+	(i32.div_u (i32.const 3) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.rem_u (i32.const 17) (i32.const 5))
+	(i32.const 2)
+	call $assert_eq
+	(i32.rem_u (i32.const -1) (i32.const 16))
+	(i32.const 15)
+	call $assert_eq
+	(i32.div_s (i32.const 17) (i32.const 5))
+	(i32.const 3)
+	call $assert_eq
+	(i32.div_s (i32.const -17) (i32.const 5))
+	(i32.const -3)
+	call $assert_eq
+	(i32.div_s (i32.const 17) (i32.const -5))
+	(i32.const -3)
+	call $assert_eq
+	(i32.div_s (i32.const -17) (i32.const -5))
+	(i32.const 3)
+	call $assert_eq
+	(i32.div_s (i32.const -2147483648) (i32.const 1))
+	(i32.const -2147483648)
+	call $assert_eq
+	(i32.div_s (i32.const -2147483648) (i32.const 2))
+	(i32.const -1073741824)
+	call $assert_eq
+	(i32.rem_s (i32.const 17) (i32.const 5))
+	(i32.const 2)
+	call $assert_eq
+	(i32.rem_s (i32.const -17) (i32.const 5))
+	(i32.const -2)
+	call $assert_eq
+	(i32.rem_s (i32.const 17) (i32.const -5))
+	(i32.const 2)
+	call $assert_eq
+	(i32.rem_s (i32.const -17) (i32.const -5))
+	(i32.const -2)
+	call $assert_eq
+	(i32.rem_s (i32.const -2147483648) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.rem_s (i32.const 0) (i32.const -7))
+	(i32.const 0)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,154 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $main
+	(i32.shl (i32.const 1) (i32.const 4))
+	(i32.const 16)
+	call $assert_eq
+	(i32.shl (i32.const 305419896) (i32.const 4))
+	(i32.const 591751040)
+	call $assert_eq
+	(i32.shl (i32.const -1) (i32.const 31))
+	(i32.const -2147483648)
+	call $assert_eq
+	(i32.shl (i32.const -2147483648) (i32.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shl (i32.const 2147483647) (i32.const 33))
+	(i32.const -2)
+	call $assert_eq
+	(i32.shl (i32.const -123456) (i32.const 3))
+	(i32.const -987648)
+	call $assert_eq
+	(i32.shl (i32.const -123456) (i32.const 32))
+	(i32.const -123456)
+	call $assert_eq
+	(i32.shl (i32.const 305419896) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shl (i32.const -2147483647) (i32.const 0))
+	(i32.const -2147483647)
+	call $assert_eq
+	(i32.shl (i32.const -2147483647) (i32.const 1))
+	(i32.const 2)
+	call $assert_eq
+	(i32.shr_s (i32.const 1) (i32.const 4))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shr_s (i32.const 305419896) (i32.const 4))
+	(i32.const 19088743)
+	call $assert_eq
+	(i32.shr_s (i32.const -1) (i32.const 31))
+	(i32.const -1)
+	call $assert_eq
+	(i32.shr_s (i32.const -2147483648) (i32.const 1))
+	(i32.const -1073741824)
+	call $assert_eq
+	(i32.shr_s (i32.const 2147483647) (i32.const 33))
+	(i32.const 1073741823)
+	call $assert_eq
+	(i32.shr_s (i32.const -123456) (i32.const 3))
+	(i32.const -15432)
+	call $assert_eq
+	(i32.shr_s (i32.const -123456) (i32.const 32))
+	(i32.const -123456)
+	call $assert_eq
+	(i32.shr_s (i32.const 305419896) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shr_s (i32.const -2147483647) (i32.const 0))
+	(i32.const -2147483647)
+	call $assert_eq
+	(i32.shr_s (i32.const -2147483647) (i32.const 1))
+	(i32.const -1073741824)
+	call $assert_eq
+	(i32.shr_u (i32.const 1) (i32.const 4))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shr_u (i32.const 305419896) (i32.const 4))
+	(i32.const 19088743)
+	call $assert_eq
+	(i32.shr_u (i32.const -1) (i32.const 31))
+	(i32.const 1)
+	call $assert_eq
+	(i32.shr_u (i32.const -2147483648) (i32.const 1))
+	(i32.const 1073741824)
+	call $assert_eq
+	(i32.shr_u (i32.const 2147483647) (i32.const 33))
+	(i32.const 1073741823)
+	call $assert_eq
+	(i32.shr_u (i32.const -123456) (i32.const 3))
+	(i32.const 536855480)
+	call $assert_eq
+	(i32.shr_u (i32.const -123456) (i32.const 32))
+	(i32.const -123456)
+	call $assert_eq
+	(i32.shr_u (i32.const 305419896) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.shr_u (i32.const -2147483647) (i32.const 0))
+	(i32.const -2147483647)
+	call $assert_eq
+	(i32.shr_u (i32.const -2147483647) (i32.const 1))
+	(i32.const 1073741824)
+	call $assert_eq
+	(i32.rotl (i32.const 1) (i32.const 4))
+	(i32.const 16)
+	call $assert_eq
+	(i32.rotl (i32.const 305419896) (i32.const 4))
+	(i32.const 591751041)
+	call $assert_eq
+	(i32.rotl (i32.const -1) (i32.const 31))
+	(i32.const -1)
+	call $assert_eq
+	(i32.rotl (i32.const -2147483648) (i32.const 1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.rotl (i32.const 2147483647) (i32.const 33))
+	(i32.const -2)
+	call $assert_eq
+	(i32.rotl (i32.const -123456) (i32.const 3))
+	(i32.const -987641)
+	call $assert_eq
+	(i32.rotl (i32.const -123456) (i32.const 32))
+	(i32.const -123456)
+	call $assert_eq
+	(i32.rotl (i32.const 305419896) (i32.const -1))
+	(i32.const 152709948)
+	call $assert_eq
+	(i32.rotl (i32.const -2147483647) (i32.const 0))
+	(i32.const -2147483647)
+	call $assert_eq
+	(i32.rotl (i32.const -2147483647) (i32.const 1))
+	(i32.const 3)
+	call $assert_eq
+	(i32.rotr (i32.const 1) (i32.const 4))
+	(i32.const 268435456)
+	call $assert_eq
+	(i32.rotr (i32.const 305419896) (i32.const 4))
+	(i32.const -2128394905)
+	call $assert_eq
+	(i32.rotr (i32.const -1) (i32.const 31))
+	(i32.const -1)
+	call $assert_eq
+	(i32.rotr (i32.const -2147483648) (i32.const 1))
+	(i32.const 1073741824)
+	call $assert_eq
+	(i32.rotr (i32.const 2147483647) (i32.const 33))
+	(i32.const -1073741825)
+	call $assert_eq
+	(i32.rotr (i32.const -123456) (i32.const 3))
+	(i32.const 536855480)
+	call $assert_eq
+	(i32.rotr (i32.const -123456) (i32.const 32))
+	(i32.const -123456)
+	call $assert_eq
+	(i32.rotr (i32.const 305419896) (i32.const -1))
+	(i32.const 610839792)
+	call $assert_eq
+	(i32.rotr (i32.const -2147483647) (i32.const 0))
+	(i32.const -2147483647)
+	call $assert_eq
+	(i32.rotr (i32.const -2147483647) (i32.const 1))
+	(i32.const -1073741824)
+	call $assert_eq)
+ (start $main))
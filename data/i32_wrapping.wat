@@ -0,0 +1,38 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (global $min (mut i32) (i32.const -2147483648))
+ (func $main
+	(i32.add (i32.const 0xFFFFFFFF) (i32.const 1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.add (i32.const -5) (i32.const 3))
+	(i32.const -2)
+	call $assert_eq
+	(i32.sub (i32.const 7) (i32.const 3))
+	(i32.const 4)
+	call $assert_eq
+	(i32.sub (i32.const 0) (i32.const 1))
+	(i32.const -1)
+	call $assert_eq
+	(i32.sub (global.get $min) (i32.const 1))
+	(i32.const 0x7FFFFFFF)
+	call $assert_eq
+	(i32.lt_s (i32.const -1) (i32.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i32.lt_s (i32.const 0) (i32.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i32.lt_s (global.get $min) (i32.const 0x7FFFFFFF))
+	(i32.const 1)
+	call $assert_eq
+	(i32.lt_u (i32.const -1) (i32.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i32.lt_u (i32.const 3) (i32.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i32.eq (i32.add (i32.const -1) (i32.const -1)) (i32.const -2))
+	(i32.const 1)
+	call $assert_eq)
+ (start $main))
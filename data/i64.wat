@@ -0,0 +1,518 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $assert_eq64 (param $lhs i64) (param $rhs i64)
+	(i32.wrap_i64 (local.get $lhs))
+	(i32.wrap_i64 (local.get $rhs))
+	call $assert_eq
+	(i32.wrap_i64 (i64.shr_u (local.get $lhs) (i64.const 32)))
+	(i32.wrap_i64 (i64.shr_u (local.get $rhs) (i64.const 32)))
+	call $assert_eq)
+ (global $g (mut i64) (i64.const -2))
+ (func $main (local $x i64)
+	(i64.add (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const -9223372036854775808)
+	call $assert_eq64
+	(i64.sub (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775806)
+	call $assert_eq64
+	(i64.mul (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775807)
+	call $assert_eq64
+	(i64.div_u (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775807)
+	call $assert_eq64
+	(i64.rem_u (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.and (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.or (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775807)
+	call $assert_eq64
+	(i64.xor (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775806)
+	call $assert_eq64
+	(i64.div_s (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 9223372036854775807)
+	call $assert_eq64
+	(i64.rem_s (i64.const 9223372036854775807) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.add (i64.const -1) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.sub (i64.const -1) (i64.const 1))
+	(i64.const -2)
+	call $assert_eq64
+	(i64.mul (i64.const -1) (i64.const 1))
+	(i64.const -1)
+	call $assert_eq64
+	(i64.div_u (i64.const -1) (i64.const 1))
+	(i64.const -1)
+	call $assert_eq64
+	(i64.rem_u (i64.const -1) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.and (i64.const -1) (i64.const 1))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.or (i64.const -1) (i64.const 1))
+	(i64.const -1)
+	call $assert_eq64
+	(i64.xor (i64.const -1) (i64.const 1))
+	(i64.const -2)
+	call $assert_eq64
+	(i64.div_s (i64.const -1) (i64.const 1))
+	(i64.const -1)
+	call $assert_eq64
+	(i64.rem_s (i64.const -1) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.add (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 2459565876494606865)
+	call $assert_eq64
+	(i64.sub (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 163971058432973775)
+	call $assert_eq64
+	(i64.mul (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 2465395958572223728)
+	call $assert_eq64
+	(i64.div_u (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_u (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 163971058432973775)
+	call $assert_eq64
+	(i64.and (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 154321028986978848)
+	call $assert_eq64
+	(i64.or (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 2305244847507628017)
+	call $assert_eq64
+	(i64.xor (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 2150923818520649169)
+	call $assert_eq64
+	(i64.div_s (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_s (i64.const 1311768467463790320) (i64.const 1147797409030816545))
+	(i64.const 163971058432973775)
+	call $assert_eq64
+	(i64.add (i64.const -17) (i64.const 5))
+	(i64.const -12)
+	call $assert_eq64
+	(i64.sub (i64.const -17) (i64.const 5))
+	(i64.const -22)
+	call $assert_eq64
+	(i64.mul (i64.const -17) (i64.const 5))
+	(i64.const -85)
+	call $assert_eq64
+	(i64.div_u (i64.const -17) (i64.const 5))
+	(i64.const 3689348814741910319)
+	call $assert_eq64
+	(i64.rem_u (i64.const -17) (i64.const 5))
+	(i64.const 4)
+	call $assert_eq64
+	(i64.and (i64.const -17) (i64.const 5))
+	(i64.const 5)
+	call $assert_eq64
+	(i64.or (i64.const -17) (i64.const 5))
+	(i64.const -17)
+	call $assert_eq64
+	(i64.xor (i64.const -17) (i64.const 5))
+	(i64.const -22)
+	call $assert_eq64
+	(i64.div_s (i64.const -17) (i64.const 5))
+	(i64.const -3)
+	call $assert_eq64
+	(i64.rem_s (i64.const -17) (i64.const 5))
+	(i64.const -2)
+	call $assert_eq64
+	(i64.add (i64.const 17) (i64.const -5))
+	(i64.const 12)
+	call $assert_eq64
+	(i64.sub (i64.const 17) (i64.const -5))
+	(i64.const 22)
+	call $assert_eq64
+	(i64.mul (i64.const 17) (i64.const -5))
+	(i64.const -85)
+	call $assert_eq64
+	(i64.div_u (i64.const 17) (i64.const -5))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.rem_u (i64.const 17) (i64.const -5))
+	(i64.const 17)
+	call $assert_eq64
+	(i64.and (i64.const 17) (i64.const -5))
+	(i64.const 17)
+	call $assert_eq64
+	(i64.or (i64.const 17) (i64.const -5))
+	(i64.const -5)
+	call $assert_eq64
+	(i64.xor (i64.const 17) (i64.const -5))
+	(i64.const -22)
+	call $assert_eq64
+	(i64.div_s (i64.const 17) (i64.const -5))
+	(i64.const -3)
+	call $assert_eq64
+	(i64.rem_s (i64.const 17) (i64.const -5))
+	(i64.const 2)
+	call $assert_eq64
+	(i64.add (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 8589934590)
+	call $assert_eq64
+	(i64.sub (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.mul (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const -8589934591)
+	call $assert_eq64
+	(i64.div_u (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_u (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.and (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 4294967295)
+	call $assert_eq64
+	(i64.or (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 4294967295)
+	call $assert_eq64
+	(i64.xor (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.div_s (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_s (i64.const 4294967295) (i64.const 4294967295))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.add (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.sub (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.mul (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.div_u (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_u (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.and (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const -9223372036854775808)
+	call $assert_eq64
+	(i64.or (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const -9223372036854775808)
+	call $assert_eq64
+	(i64.xor (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.div_s (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rem_s (i64.const -9223372036854775808) (i64.const -9223372036854775808))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.add (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1099511627779)
+	call $assert_eq64
+	(i64.sub (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1099511627773)
+	call $assert_eq64
+	(i64.mul (i64.const 1099511627776) (i64.const 3))
+	(i64.const 3298534883328)
+	call $assert_eq64
+	(i64.div_u (i64.const 1099511627776) (i64.const 3))
+	(i64.const 366503875925)
+	call $assert_eq64
+	(i64.rem_u (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.and (i64.const 1099511627776) (i64.const 3))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.or (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1099511627779)
+	call $assert_eq64
+	(i64.xor (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1099511627779)
+	call $assert_eq64
+	(i64.div_s (i64.const 1099511627776) (i64.const 3))
+	(i64.const 366503875925)
+	call $assert_eq64
+	(i64.rem_s (i64.const 1099511627776) (i64.const 3))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.shl (i64.const 1) (i64.const 63))
+	(i64.const -9223372036854775808)
+	call $assert_eq64
+	(i64.shr_u (i64.const 1) (i64.const 63))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.shr_s (i64.const 1) (i64.const 63))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.rotl (i64.const 1) (i64.const 63))
+	(i64.const -9223372036854775808)
+	call $assert_eq64
+	(i64.rotr (i64.const 1) (i64.const 63))
+	(i64.const 2)
+	call $assert_eq64
+	(i64.shl (i64.const -9223372036854775808) (i64.const 1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.shr_u (i64.const -9223372036854775808) (i64.const 1))
+	(i64.const 4611686018427387904)
+	call $assert_eq64
+	(i64.shr_s (i64.const -9223372036854775808) (i64.const 1))
+	(i64.const -4611686018427387904)
+	call $assert_eq64
+	(i64.rotl (i64.const -9223372036854775808) (i64.const 1))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.rotr (i64.const -9223372036854775808) (i64.const 1))
+	(i64.const 4611686018427387904)
+	call $assert_eq64
+	(i64.shl (i64.const -5) (i64.const 65))
+	(i64.const -10)
+	call $assert_eq64
+	(i64.shr_u (i64.const -5) (i64.const 65))
+	(i64.const 9223372036854775805)
+	call $assert_eq64
+	(i64.shr_s (i64.const -5) (i64.const 65))
+	(i64.const -3)
+	call $assert_eq64
+	(i64.rotl (i64.const -5) (i64.const 65))
+	(i64.const -9)
+	call $assert_eq64
+	(i64.rotr (i64.const -5) (i64.const 65))
+	(i64.const -3)
+	call $assert_eq64
+	(i64.shl (i64.const 1311768467463790320) (i64.const 36))
+	(i64.const -6066930339719151616)
+	call $assert_eq64
+	(i64.shr_u (i64.const 1311768467463790320) (i64.const 36))
+	(i64.const 19088743)
+	call $assert_eq64
+	(i64.shr_s (i64.const 1311768467463790320) (i64.const 36))
+	(i64.const 19088743)
+	call $assert_eq64
+	(i64.rotl (i64.const 1311768467463790320) (i64.const 36))
+	(i64.const -6066930334832433271)
+	call $assert_eq64
+	(i64.rotr (i64.const 1311768467463790320) (i64.const 36))
+	(i64.const -8526495043095935641)
+	call $assert_eq64
+	(i64.shl (i64.const -9223372036854775808) (i64.const 63))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.shr_u (i64.const -9223372036854775808) (i64.const 63))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.shr_s (i64.const -9223372036854775808) (i64.const 63))
+	(i64.const -1)
+	call $assert_eq64
+	(i64.rotl (i64.const -9223372036854775808) (i64.const 63))
+	(i64.const 4611686018427387904)
+	call $assert_eq64
+	(i64.rotr (i64.const -9223372036854775808) (i64.const 63))
+	(i64.const 1)
+	call $assert_eq64
+	(i64.shl (i64.const 240) (i64.const -1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.shr_u (i64.const 240) (i64.const -1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.shr_s (i64.const 240) (i64.const -1))
+	(i64.const 0)
+	call $assert_eq64
+	(i64.rotl (i64.const 240) (i64.const -1))
+	(i64.const 120)
+	call $assert_eq64
+	(i64.rotr (i64.const 240) (i64.const -1))
+	(i64.const 480)
+	call $assert_eq64
+	(i64.eq (i64.const -1) (i64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ne (i64.const -1) (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_s (i64.const -1) (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_u (i64.const -1) (i64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_s (i64.const -1) (i64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_u (i64.const -1) (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_s (i64.const -1) (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_u (i64.const -1) (i64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ge_s (i64.const -1) (i64.const 0))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ge_u (i64.const -1) (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.eq (i64.const 0) (i64.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ne (i64.const 0) (i64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_s (i64.const 0) (i64.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i64.lt_u (i64.const 0) (i64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i64.gt_s (i64.const 0) (i64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i64.gt_u (i64.const 0) (i64.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i64.le_s (i64.const 0) (i64.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i64.le_u (i64.const 0) (i64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ge_s (i64.const 0) (i64.const -1))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ge_u (i64.const 0) (i64.const -1))
+	(i32.const 0)
+	call $assert_eq
+	(i64.eq (i64.const 5) (i64.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ne (i64.const 5) (i64.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i64.lt_s (i64.const 5) (i64.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i64.lt_u (i64.const 5) (i64.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_s (i64.const 5) (i64.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_u (i64.const 5) (i64.const 5))
+	(i32.const 0)
+	call $assert_eq
+	(i64.le_s (i64.const 5) (i64.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_u (i64.const 5) (i64.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ge_s (i64.const 5) (i64.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ge_u (i64.const 5) (i64.const 5))
+	(i32.const 1)
+	call $assert_eq
+	(i64.eq (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ne (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_s (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 0)
+	call $assert_eq
+	(i64.lt_u (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_s (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 1)
+	call $assert_eq
+	(i64.gt_u (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_s (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 0)
+	call $assert_eq
+	(i64.le_u (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ge_s (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 1)
+	call $assert_eq
+	(i64.ge_u (i64.const 4294967296) (i64.const 4294967295))
+	(i32.const 1)
+	call $assert_eq
+	(i64.eq (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ne (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_s (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 1)
+	call $assert_eq
+	(i64.lt_u (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_s (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 0)
+	call $assert_eq
+	(i64.gt_u (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_s (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 1)
+	call $assert_eq
+	(i64.le_u (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ge_s (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 0)
+	call $assert_eq
+	(i64.ge_u (i64.const -9223372036854775808) (i64.const 9223372036854775807))
+	(i32.const 1)
+	call $assert_eq
+	(i64.eqz (i64.const 0))
+	(i32.const 1)
+	call $assert_eq
+	(i64.eqz (i64.const 4294967296))
+	(i32.const 0)
+	call $assert_eq
+	(i64.extend_i32_s (i32.const -3))
+	(i64.const -3)
+	call $assert_eq64
+	(i64.extend_i32_s (i32.const 0x7FFFFFFF))
+	(i64.const 0x7FFFFFFF)
+	call $assert_eq64
+	(i64.extend_i32_u (i32.const -3))
+	(i64.const 0xFFFFFFFD)
+	call $assert_eq64
+	(i32.wrap_i64 (i64.const 0x1234567887654321))
+	(i32.const 0x87654321)
+	call $assert_eq
+	(global.get $g)
+	(i64.const -2)
+	call $assert_eq64
+	(local.set $x (i64.const 0xFFFFFFFF))
+	(local.set $x (i64.add (local.get $x) (i64.const 1)))
+	(local.get $x)
+	(i64.const 0x100000000)
+	call $assert_eq64)
+ (start $main))
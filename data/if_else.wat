@@ -0,0 +1,39 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $main
+	(local $x i32)
+	(local.set $x (i32.const 0))
+	;; Empty block type, condition is true.
+	(if (i32.const 1)
+	 (then (local.set $x (i32.const 7))))
+	(local.get $x)
+	(i32.const 7)
+	call $assert_eq
+	;; Empty block type without else, condition is false.
+	(if (i32.const 0)
+	 (then (local.set $x (i32.const 8))))
+	(local.get $x)
+	(i32.const 7)
+	call $assert_eq
+	;; Single result.
+	(if (result i32)
+	 (i32.eq (local.get $x) (i32.const 7))
+	 (then (i32.const 10))
+	 (else (i32.const 20)))
+	(i32.const 10)
+	call $assert_eq
+	(if (result i32)
+	 (i32.eq (local.get $x) (i32.const 3))
+	 (then (i32.const 10))
+	 (else (i32.const 20)))
+	(i32.const 20)
+	call $assert_eq
+	;; Type-indexed block type with a parameter.
+	(i32.const 5)
+	(local.get $x)
+	(if (param i32) (result i32)
+	 (then (i32.const 1) (i32.add))
+	 (else (i32.const 2) (i32.add)))
+	(i32.const 6)
+	call $assert_eq)
+ (start $main))
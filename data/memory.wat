@@ -0,0 +1,44 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (func $main
+	;; Aligned access with an offset.
+	(i32.store offset=64 (i32.const 4) (i32.const 0x11223344))
+	(i32.load (i32.const 68))
+	(i32.const 0x11223344)
+	call $assert_eq
+	;; Unaligned access inside of a single word.
+	(i32.store (i32.const 5) (i32.const 0x55667788))
+	(i32.load offset=1 (i32.const 4))
+	(i32.const 0x55667788)
+	call $assert_eq
+	;; Bytes around a store are preserved.
+	(i32.store (i32.const 9) (i32.const 0x01020304))
+	(i32.load (i32.const 6))
+	(i32.const 0x04556677)
+	call $assert_eq
+	;; Access that spans two words.
+	(i32.store (i32.const 30) (i32.const 0x0a0b0c0d))
+	(i32.load (i32.const 30))
+	(i32.const 0x0a0b0c0d)
+	call $assert_eq
+	(i32.load (i32.const 29))
+	(i32.const 0x0b0c0d00)
+	call $assert_eq
+	(i32.load (i32.const 32))
+	(i32.const 0x00000a0b)
+	call $assert_eq
+	;; Overwrite part of the spanning value from the next word.
+	(i32.store (i32.const 31) (i32.const 0x7f))
+	(i32.load (i32.const 30))
+	(i32.const 0x00007f0d)
+	call $assert_eq
+	;; The top byte of a word.
+	(i32.store (i32.const 95) (i32.const 0x12345678))
+	(i32.load (i32.const 92))
+	(i32.const 0x78000000)
+	call $assert_eq
+	(i32.load (i32.const 96))
+	(i32.const 0x00123456)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,22 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1 2)
+ (func $main
+	;; The last bytes of the memory are accessible.
+	(i32.store (i32.const 0xfffc) (i32.const 42))
+	(i32.load (i32.const 0xfffc))
+	(i32.const 42)
+	call $assert_eq
+	(i32.store8 offset=0xffff (i32.const 0) (i32.const 7))
+	(i32.load8_u (i32.const 0xffff))
+	(i32.const 7)
+	call $assert_eq
+	;; So are the bytes of a new page.
+	(memory.grow (i32.const 1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.store (i32.const 0x1fffc) (i32.const 9))
+	(i32.load offset=0xfffc (i32.const 0x10000))
+	(i32.const 9)
+	call $assert_eq)
+ (start $main))
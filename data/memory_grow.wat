@@ -0,0 +1,32 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1 4)
+ (func $main
+	(memory.size)
+	(i32.const 1)
+	call $assert_eq
+	(memory.grow (i32.const 2))
+	(i32.const 1)
+	call $assert_eq
+	(memory.size)
+	(i32.const 3)
+	call $assert_eq
+	;; Growing past the declared maximum fails and keeps the size.
+	(memory.grow (i32.const 2))
+	(i32.const -1)
+	call $assert_eq
+	(memory.size)
+	(i32.const 3)
+	call $assert_eq
+	(memory.grow (i32.const 1))
+	(i32.const 3)
+	call $assert_eq
+	(memory.grow (i32.const 0))
+	(i32.const 4)
+	call $assert_eq
+	;; The new pages are usable.
+	(i32.store (i32.const 0x3fffc) (i32.const 7))
+	(i32.load (i32.const 0x3fffc))
+	(i32.const 7)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,96 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1)
+ (func $main
+	(i32.store (i32.const 0) (i32.const 0x8071f0f1))
+	;; Narrow unsigned loads.
+	(i32.load8_u (i32.const 0))
+	(i32.const 0xf1)
+	call $assert_eq
+	(i32.load16_u (i32.const 1))
+	(i32.const 0x71f0)
+	call $assert_eq
+	;; Narrow signed loads, checked through their bytes.
+	(i32.store (i32.const 64) (i32.load8_s (i32.const 3)))
+	(i32.load16_u (i32.const 64))
+	(i32.const 0xff80)
+	call $assert_eq
+	(i32.store (i32.const 64) (i32.load16_s (i32.const 1)))
+	(i32.load16_u (i32.const 66))
+	(i32.const 0)
+	call $assert_eq
+	(i32.load16_s (i32.const 1))
+	(i32.const 0x71f0)
+	call $assert_eq
+	;; Narrow stores keep the surrounding bytes.
+	(i32.store8 (i32.const 1) (i32.const 0x1234))
+	(i32.store16 (i32.const 2) (i32.const 0x56789abc))
+	(i32.load (i32.const 0))
+	(i32.const 0x9abc34f1)
+	call $assert_eq
+	;; Narrow stores across a word boundary.
+	(i32.store (i32.const 28) (i32.const 0x44332211))
+	(i32.store (i32.const 32) (i32.const 0x88776655))
+	(i32.store16 (i32.const 31) (i32.const 0xaabb))
+	(i32.load (i32.const 28))
+	(i32.const 0xbb332211)
+	call $assert_eq
+	(i32.load (i32.const 32))
+	(i32.const 0x887766aa)
+	call $assert_eq
+	;; 64-bit loads and stores, including across a word boundary.
+	(i64.store (i32.const 100) (i64.load (i32.const 28)))
+	(i32.load (i32.const 100))
+	(i32.const 0xbb332211)
+	call $assert_eq
+	(i32.load (i32.const 104))
+	(i32.const 0x887766aa)
+	call $assert_eq
+	(i64.store (i32.const 126) (i64.load (i32.const 28)))
+	(i32.load (i32.const 126))
+	(i32.const 0xbb332211)
+	call $assert_eq
+	(i32.load (i32.const 130))
+	(i32.const 0x887766aa)
+	call $assert_eq
+	;; 64-bit narrow loads and stores.
+	(i64.store (i32.const 200) (i64.load8_s (i32.const 31)))
+	(i32.load (i32.const 200))
+	(i32.const 0xffffffbb)
+	call $assert_eq
+	(i32.load (i32.const 204))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i64.store (i32.const 200) (i64.load16_u (i32.const 31)))
+	(i32.load (i32.const 200))
+	(i32.const 0xaabb)
+	call $assert_eq
+	(i32.load (i32.const 204))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 200) (i64.load32_s (i32.const 32)))
+	(i32.load (i32.const 204))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i64.store (i32.const 200) (i64.load32_u (i32.const 32)))
+	(i32.load (i32.const 204))
+	(i32.const 0)
+	call $assert_eq
+	(i64.store (i32.const 200) (i64.load16_s (i32.const 28)))
+	(i32.load (i32.const 200))
+	(i32.const 0x2211)
+	call $assert_eq
+	(i64.store (i32.const 200) (i64.load8_u (i32.const 35)))
+	(i32.load (i32.const 200))
+	(i32.const 0x88)
+	call $assert_eq
+	(i64.store8 (i32.const 208) (i64.load (i32.const 28)))
+	(i64.store16 (i32.const 209) (i64.load (i32.const 28)))
+	(i64.store32 (i32.const 211) (i64.load (i32.const 28)))
+	(i32.load (i32.const 208))
+	(i32.const 0x11221111)
+	call $assert_eq
+	(i32.load (i32.const 212))
+	(i32.const 0x00bb3322)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,8 @@
+(module
+ (memory 1)
+ (func $main
+	;; The effective address overflows 32 bits, which must not wrap around
+	;; to the start of the memory.
+	(i32.load offset=0xffffffff (i32.const 1))
+	drop)
+ (start $main))
@@ -0,0 +1,15 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (memory 1 2)
+ (func $main
+	(memory.grow (i32.const 1))
+	(i32.const 1)
+	call $assert_eq
+	;; The last four bytes of the memory can be loaded.
+	(i32.load (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 4)))
+	(i32.const 0)
+	call $assert_eq
+	;; One byte of this load is past the end of the memory.
+	(i32.load (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 3)))
+	drop)
+ (start $main))
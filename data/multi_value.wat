@@ -0,0 +1,155 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (type $pair (func (result i32 i32)))
+ (type $pair_to_pair (func (param i32 i32) (result i32 i32)))
+ (func $swap (param i32 i32) (result i32 i32)
+	(local.get 1)
+	(local.get 0))
+ ;; Results of different types, with a local between the parameters and the results.
+ (func $split (param $value i64) (result i32 i64 i32)
+	(local $low i32)
+	(local.set $low (i32.wrap_i64 (local.get $value)))
+	(local.get $low)
+	(i64.shr_u (local.get $value) (i64.const 32))
+	(i32.add (local.get $low) (i32.const 1)))
+ ;; More results than the frame has slots.
+ (func $triple (result i32 i32 i32)
+	(i32.const 1)
+	(i32.const 2)
+	(i32.const 3))
+ ;; Returns `(fib(n), fib(n + 1))`.
+ (func $fib_pair (param $n i32) (result i32 i32)
+	(if (type $pair) (i32.eqz (local.get $n))
+	 (then (i32.const 0) (i32.const 1))
+	 (else
+		(call $fib_pair (i32.sub (local.get $n) (i32.const 1)))
+		(call $step))))
+ (func $step (param $a i32) (param $b i32) (result i32 i32)
+	(local.get $b)
+	(i32.add (local.get $a) (local.get $b)))
+ ;; Sums `1..=n` with a loop that carries the counter and the sum.
+ (func $sum (param $n i32) (result i32)
+	(i32.const 0)
+	(local.get $n)
+	(loop $next (param i32 i32) (result i32)
+		(local.tee $n)
+		(i32.add)
+		(i32.sub (local.get $n) (i32.const 1))
+		(local.tee $n)
+		(local.get $n)
+		(br_if $next)
+		(drop)))
+ ;; Leaves the block with two values from either of its branches.
+ (func $choose (param $which i32) (result i32 i32)
+	(block $done (result i32 i32)
+		(i32.const 1)
+		(i32.const 2)
+		(br_if $done (i32.eqz (local.get $which)))
+		(call $swap)
+		(br $done)))
+ ;; Branches out of a block with an extra value below its results.
+ (func $extra (param $taken i32) (result i32 i32)
+	(block (result i32 i32)
+		(i32.const 9)
+		(i32.const 1)
+		(i32.const 2)
+		(br_if 0 (local.get $taken))
+		(i32.add)))
+ (func $main
+	(local $a i32)
+	(local $b i32)
+	(call $swap (i32.const 1) (i32.const 2))
+	(local.set $b)
+	(local.set $a)
+	(local.get $a)
+	(i32.const 2)
+	call $assert_eq
+	(local.get $b)
+	(i32.const 1)
+	call $assert_eq
+
+	(call $split (i64.const 0x1234567800000007))
+	(i32.const 8)
+	call $assert_eq
+	(i32.wrap_i64)
+	(i32.const 0x12345678)
+	call $assert_eq
+	(i32.const 7)
+	call $assert_eq
+
+	(call $triple)
+	(i32.const 3)
+	call $assert_eq
+	(i32.const 2)
+	call $assert_eq
+	(i32.const 1)
+	call $assert_eq
+
+	(call $fib_pair (i32.const 10))
+	(i32.const 89)
+	call $assert_eq
+	(i32.const 55)
+	call $assert_eq
+
+	(call $sum (i32.const 10))
+	(i32.const 55)
+	call $assert_eq
+
+	(call $choose (i32.const 0))
+	(i32.const 2)
+	call $assert_eq
+	(i32.const 1)
+	call $assert_eq
+	(call $choose (i32.const 1))
+	(i32.const 1)
+	call $assert_eq
+	(i32.const 2)
+	call $assert_eq
+
+	(call $extra (i32.const 1))
+	(i32.const 2)
+	call $assert_eq
+	(i32.const 1)
+	call $assert_eq
+	(call $extra (i32.const 0))
+	(i32.const 3)
+	call $assert_eq
+	(i32.const 9)
+	call $assert_eq
+
+	;; Blocks with parameters and several results.
+	(i32.const 3)
+	(i32.const 4)
+	(block $b (type $pair_to_pair)
+		(i32.mul)
+		(i32.const 5))
+	(i32.const 5)
+	call $assert_eq
+	(i32.const 12)
+	call $assert_eq
+	(i32.const 3)
+	(i32.const 4)
+	(if (type $pair_to_pair) (i32.const 0)
+	 (then (call $swap))
+	 (else (i32.add) (i32.const 9)))
+	(i32.const 9)
+	call $assert_eq
+	(i32.const 7)
+	call $assert_eq
+	(i32.const 3)
+	(i32.const 4)
+	(if (type $pair_to_pair) (i32.const 1)
+	 (then (call $swap)))
+	(i32.const 3)
+	call $assert_eq
+	(i32.const 4)
+	call $assert_eq
+	(i32.const 3)
+	(i32.const 4)
+	(if (type $pair_to_pair) (i32.const 0)
+	 (then (call $swap)))
+	(i32.const 4)
+	call $assert_eq
+	(i32.const 3)
+	call $assert_eq)
+ (start $main))
@@ -0,0 +1,71 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $assert_eq64 (param $lhs i64) (param $rhs i64)
+	(i32.wrap_i64 (local.get $lhs))
+	(i32.wrap_i64 (local.get $rhs))
+	call $assert_eq
+	(i32.wrap_i64 (i64.shr_u (local.get $lhs) (i64.const 32)))
+	(i32.wrap_i64 (i64.shr_u (local.get $rhs) (i64.const 32)))
+	call $assert_eq)
+ (func $main
+	(i32.extend8_s (i32.const 0x0))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.extend8_s (i32.const 0x7f))
+	(i32.const 0x7f)
+	call $assert_eq
+	(i32.extend8_s (i32.const 0x80))
+	(i32.const 0xffffff80)
+	call $assert_eq
+	(i32.extend8_s (i32.const 0x1ff))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.extend8_s (i32.const 0x12345680))
+	(i32.const 0xffffff80)
+	call $assert_eq
+	(i32.extend8_s (i32.const 0xffff0042))
+	(i32.const 0x42)
+	call $assert_eq
+	(i32.extend16_s (i32.const 0x7fff))
+	(i32.const 0x7fff)
+	call $assert_eq
+	(i32.extend16_s (i32.const 0x8000))
+	(i32.const 0xffff8000)
+	call $assert_eq
+	(i32.extend16_s (i32.const 0x17fff))
+	(i32.const 0x7fff)
+	call $assert_eq
+	(i32.extend16_s (i32.const 0xdeadbeef))
+	(i32.const 0xffffbeef)
+	call $assert_eq
+	(i64.extend8_s (i64.const 0x7f))
+	(i64.const 0x7f)
+	call $assert_eq64
+	(i64.extend8_s (i64.const 0x80))
+	(i64.const 0xffffffffffffff80)
+	call $assert_eq64
+	(i64.extend8_s (i64.const 0xffffffffffffff01))
+	(i64.const 0x1)
+	call $assert_eq64
+	(i64.extend16_s (i64.const 0x8000))
+	(i64.const 0xffffffffffff8000)
+	call $assert_eq64
+	(i64.extend16_s (i64.const 0x123457fff))
+	(i64.const 0x7fff)
+	call $assert_eq64
+	(i64.extend16_s (i64.const 0xffffffffffff0000))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.extend32_s (i64.const 0x80000000))
+	(i64.const 0xffffffff80000000)
+	call $assert_eq64
+	(i64.extend32_s (i64.const 0x123456789))
+	(i64.const 0x23456789)
+	call $assert_eq64
+	(i64.extend32_s (i64.const 0xffffffff7fffffff))
+	(i64.const 0x7fffffff)
+	call $assert_eq64
+	(i64.extend32_s (i64.const 0x0))
+	(i64.const 0x0)
+	call $assert_eq64)
+ (start $main))
@@ -0,0 +1,7 @@
+(module
+ (func $truncate (param $x f64) (result i32)
+	(i32.trunc_f64_s (local.get $x)))
+ (func $main
+	(call $truncate (f64.const nan))
+	drop)
+ (start $main))
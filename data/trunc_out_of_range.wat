@@ -0,0 +1,8 @@
+(module
+ (func $truncate (param $x f32) (result i32)
+	(i32.trunc_f32_u (local.get $x)))
+ (func $main
+	;; 2^32 is the smallest f32 above the u32 range.
+	(call $truncate (f32.const 4294967296))
+	drop)
+ (start $main))
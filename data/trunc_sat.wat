@@ -0,0 +1,203 @@
+(module
+ (import "env" "assert_eq" (func $assert_eq (param i32) (param i32)))
+ (func $assert_eq64 (param $lhs i64) (param $rhs i64)
+	(i32.wrap_i64 (local.get $lhs))
+	(i32.wrap_i64 (local.get $rhs))
+	call $assert_eq
+	(i32.wrap_i64 (i64.shr_u (local.get $lhs) (i64.const 32)))
+	(i32.wrap_i64 (i64.shr_u (local.get $rhs) (i64.const 32)))
+	call $assert_eq)
+ (func $main
+	(i32.trunc_sat_f32_s (f32.const nan))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const -inf))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const inf))
+	(i32.const 0x7fffffff)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const -1.9))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const 0x1p70))
+	(i32.const 0x7fffffff)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const -0x1p70))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const 1e9))
+	(i32.const 0x3b9aca00)
+	call $assert_eq
+	(i32.trunc_sat_f32_s (f32.const -0x1.fffffep30))
+	(i32.const 0x80000080)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const nan))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const -inf))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const inf))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const -1.9))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const 0x1p70))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const -0x1p70))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const 1e9))
+	(i32.const 0x3b9aca00)
+	call $assert_eq
+	(i32.trunc_sat_f32_u (f32.const -0x1.fffffep30))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const nan))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const -inf))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const inf))
+	(i32.const 0x7fffffff)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const -1.9))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const 0x1p70))
+	(i32.const 0x7fffffff)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const -0x1p70))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const 1e9))
+	(i32.const 0x3b9aca00)
+	call $assert_eq
+	(i32.trunc_sat_f64_s (f64.const -2147483648.9))
+	(i32.const 0x80000000)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const nan))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const -inf))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const inf))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const -1.9))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const 0x1p70))
+	(i32.const 0xffffffff)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const -0x1p70))
+	(i32.const 0x0)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const 1e9))
+	(i32.const 0x3b9aca00)
+	call $assert_eq
+	(i32.trunc_sat_f64_u (f64.const -2147483648.9))
+	(i32.const 0x0)
+	call $assert_eq
+	(i64.trunc_sat_f32_s (f32.const nan))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const -inf))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const inf))
+	(i64.const 0x7fffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const -1.9))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const 0x1p70))
+	(i64.const 0x7fffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const -0x1p70))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const 1e9))
+	(i64.const 0x3b9aca00)
+	call $assert_eq64
+	(i64.trunc_sat_f32_s (f32.const -0x1.fffffep30))
+	(i64.const 0xffffffff80000080)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const nan))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const -inf))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const inf))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const -1.9))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const 0x1p70))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const -0x1p70))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const 1e9))
+	(i64.const 0x3b9aca00)
+	call $assert_eq64
+	(i64.trunc_sat_f32_u (f32.const -0x1.fffffep30))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const nan))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const -inf))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const inf))
+	(i64.const 0x7fffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const -1.9))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const 0x1p70))
+	(i64.const 0x7fffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const -0x1p70))
+	(i64.const 0x8000000000000000)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const 1e9))
+	(i64.const 0x3b9aca00)
+	call $assert_eq64
+	(i64.trunc_sat_f64_s (f64.const -2147483648.9))
+	(i64.const 0xffffffff80000000)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const nan))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const -inf))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const inf))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const -1.9))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const 0x1p70))
+	(i64.const 0xffffffffffffffff)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const -0x1p70))
+	(i64.const 0x0)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const 1e9))
+	(i64.const 0x3b9aca00)
+	call $assert_eq64
+	(i64.trunc_sat_f64_u (f64.const -2147483648.9))
+	(i64.const 0x0)
+	call $assert_eq64)
+ (start $main))
@@ -1,7 +1,59 @@
-use wasmparser::{BlockType, BrTable, Ieee32, Ieee64, MemArg, Payload::*, ValType};
+use std::collections::{BTreeMap, HashMap};
+
+use wasmparser::{
+    BlockType, BrTable, ConstExpr, FuncType, FunctionBody, GlobalType, Ieee32, Ieee64, MemArg,
+    MemoryType, Operator, Payload::*, ValType, Validator, WasmFeatures,
+};
 
 use anyhow::Result;
 
+mod runtime;
+mod softfloat;
+
+use runtime::{
+    MemoryImage, Routine, Runtime, MAX_MEMORY_PAGES, MAX_PASSIVE_DATA_BYTES, MAX_TABLE_SIZE,
+    MEMORY_BYTES, MEMORY_MAX_PAGES, MEMORY_PAGES, PASSIVE_DATA_BASE, TABLE_BASE,
+};
+
+const WASM_PAGE_SIZE: u64 = 65536;
+// The most pages a 32-bit memory can have.
+const WASM_MAX_PAGES: u64 = 65536;
+
+/// Options of the compiler.
+pub struct Config {
+    /// The most pages the linear memory may grow to, regardless of the
+    /// maximum declared by the module. Limits above `MAX_MEMORY_PAGES` have no
+    /// effect.
+    pub max_memory_pages: u32,
+    /// Skip the bounds checks of memory accesses at constant addresses that
+    /// fit into the initial memory.
+    pub elide_constant_bounds_checks: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_memory_pages: MAX_MEMORY_PAGES as u32,
+            elide_constant_bounds_checks: false,
+        }
+    }
+}
+
+// Tables with fewer targets than this are lowered to a chain of comparisons
+// instead of a computed jump.
+const BR_TABLE_JUMP_THRESHOLD: usize = 4;
+
+// Integers are kept in registers and memory as their two's complement
+// encoding, i.e. an `i32` is always in `0..2^32`.
+const I32_BITS: u32 = 32;
+const I64_BITS: u32 = 64;
+
+// Floats are kept as their IEEE 754 bit patterns, so reinterpreting a float as
+// an integer of the same width and back is free. Only operations on the sign
+// bit are inlined, the others call the soft-float library.
+const F32_BITS: u32 = 32;
+const F64_BITS: u32 = 64;
+
 pub struct ZkAssembler {
     instructions: Vec<String>,
 }
@@ -14,6 +66,8 @@ enum Register {
     C,
     D,
     E,
+    // Return address register, set by `:CALL` and used by `:RETURN`.
+    RR,
 }
 
 impl Register {
@@ -24,6 +78,7 @@ impl Register {
             Register::C => "C",
             Register::D => "D",
             Register::E => "E",
+            Register::RR => "RR",
         }
     }
 }
@@ -48,7 +103,6 @@ impl ZkAssembler {
         self.add_instruction(&format!(":JMP({dst})"));
     }
 
-    #[allow(dead_code)]
     fn jump_if_zero(&mut self, register: Register, dst: &str) {
         self.add_instruction(&format!("{} :JMPZ({dst})", register.name()));
     }
@@ -57,14 +111,31 @@ impl ZkAssembler {
         self.add_instruction(&format!(":CALL({dst})"));
     }
 
+    fn ret(&mut self) {
+        self.add_instruction(":RETURN");
+    }
+
     fn jump_if_nonzero(&mut self, register: Register, dst: &str) {
         self.add_instruction(&format!("{} :JMPNZ({dst})", register.name()));
     }
 
-    fn stack_push_const(&mut self, value: i32) {
+    fn stack_push_const(&mut self, value: impl std::fmt::Display) {
         self.add_instruction(&format!("{value} :MSTORE(SP++)"));
     }
 
+    fn set_const(&mut self, register: Register, value: impl std::fmt::Display) {
+        self.add_instruction(&format!("{value} => {}", register.name()));
+    }
+
+    fn move_register(&mut self, src: Register, dst: Register) {
+        self.add_instruction(&format!("{} => {}", src.name(), dst.name()));
+    }
+
+    /// Jumps to the instruction `index` positions after the label `dst`.
+    fn jump_indexed(&mut self, dst: &str, index: Register) {
+        self.add_instruction(&format!(":JMP(@{dst} + {})", index.name()));
+    }
+
     fn stack_push_register(&mut self, register: Register) {
         self.add_instruction(&format!("{} :MSTORE(SP++)", register.name()));
     }
@@ -74,6 +145,12 @@ impl ZkAssembler {
         self.add_instruction(&format!("$ => {}: MLOAD(SP)", register.name()));
     }
 
+    fn stack_drop(&mut self, count: i32) {
+        if count != 0 {
+            self.add_instruction(&format!("SP - {count} => SP"));
+        }
+    }
+
     fn get_stack_address(offset: i32) -> String {
         match offset.cmp(&0) {
             std::cmp::Ordering::Less => format!("SP - {}", -offset),
@@ -90,6 +167,22 @@ impl ZkAssembler {
         ));
     }
 
+    fn variable_get(&mut self, register: Register, name: &str) {
+        self.add_instruction(&format!("$ => {} :MLOAD({name})", register.name()));
+    }
+
+    fn variable_set(&mut self, register: Register, name: &str) {
+        self.add_instruction(&format!("{} :MSTORE({name})", register.name()));
+    }
+
+    fn variable_set_const(&mut self, value: impl std::fmt::Display, name: &str) {
+        self.add_instruction(&format!("{value} :MSTORE({name})"));
+    }
+
+    fn memory_set_const(&mut self, value: impl std::fmt::Display, address: Register) {
+        self.add_instruction(&format!("{value} :MSTORE({})", address.name()));
+    }
+
     fn stack_get(&mut self, register: Register, offset: i32) {
         self.add_instruction(&format!(
             "$ => {} :MLOAD({})",
@@ -118,6 +211,12 @@ impl ZkAssembler {
         self.add_instruction(&format!("$ => {} :XOR", register.name()));
     }
 
+    /// Computes `A * B` using the arithmetic state machine, which requires
+    /// `C` and `D` to be zero.
+    fn mul(&mut self, register: Register) {
+        self.add_instruction(&format!("${{A * B}} => {} :ARITH", register.name()));
+    }
+
     fn eq(&mut self, register: Register) {
         self.add_instruction(&format!("$ => {} :EQ", register.name()));
     }
@@ -126,17 +225,13 @@ impl ZkAssembler {
         self.add_instruction(&format!("$ => {} :LT", register.name()));
     }
 
-    fn signed_less_then(&mut self, register: Register) {
-        self.add_instruction(&format!("$ => {} :SLT", register.name()));
-    }
-
     fn assert(&mut self, register: Register) {
         self.add_instruction(&format!("{} :ASSERT", register.name()));
     }
 
     #[allow(dead_code)]
     fn assert_const(&mut self, value: i32) {
-        self.add_instruction(&format!("{value} :ASSERT"));
+        self.add_instruction(&format!("{} :ASSERT", value as u32));
     }
 
     fn finalize(self) -> String {
@@ -151,8 +246,6 @@ enum Location {
     // The local is in a given register.
     #[allow(dead_code)]
     Register(Register),
-    // The local is not initialized.
-    Uninitialized,
 }
 
 struct Local {
@@ -164,6 +257,9 @@ struct Local {
 enum BlockInstr {
     Loop,
     Block,
+    // An `if` whose `else` arm has not been seen yet.
+    If,
+    Else,
     Function,
 }
 
@@ -171,6 +267,10 @@ enum BlockInstr {
 struct Block {
     index: u32,
     block_instr: BlockInstr,
+    // Stack depth at the block entry, not counting the block parameters.
+    stack_depth: i32,
+    params: i32,
+    results: i32,
 }
 
 impl Block {
@@ -178,55 +278,529 @@ impl Block {
         match self.block_instr {
             BlockInstr::Loop => format!("loop_{}", self.index),
             BlockInstr::Block => format!("block_{}", self.index),
-            BlockInstr::Function => format!("function_{}", self.index),
+            BlockInstr::If | BlockInstr::Else => format!("if_{}", self.index),
+            BlockInstr::Function => format!("return_{}", self.index),
         }
     }
+
+    fn else_label(&self) -> String {
+        format!("else_{}", self.index)
+    }
+
+    /// Number of values a branch to the block carries: the parameters of a
+    /// loop and the results of any other block.
+    fn branch_values(&self) -> i32 {
+        match self.block_instr {
+            BlockInstr::Loop => self.params,
+            _ => self.results,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Shift {
+    Left,
+    RightSigned,
+    RightUnsigned,
+    RotateLeft,
+    RotateRight,
+}
+
+/// Types of the module and of every function in its index space.
+struct ModuleContext {
+    types: Vec<FuncType>,
+    // Type index of each function, imported functions first.
+    functions: Vec<u32>,
+    memory: Option<MemoryType>,
+    globals: Vec<GlobalType>,
+    // Index of the imported `env.assert_eq`, which compiles to an `ASSERT`.
+    assert_eq: Option<u32>,
+    // Function labels are the prefix followed by the name of the function,
+    // or by its index if it has no name.
+    function_prefix: &'static str,
+    function_names: HashMap<u32, String>,
 }
 
-struct ZkCodegenVisitor {
+impl ModuleContext {
+    fn new(function_prefix: &'static str) -> Self {
+        Self {
+            types: Vec::new(),
+            functions: Vec::new(),
+            memory: None,
+            globals: Vec::new(),
+            assert_eq: None,
+            function_prefix,
+            function_names: HashMap::new(),
+        }
+    }
+
+    fn func_type(&self, function_index: u32) -> &FuncType {
+        &self.types[self.functions[function_index as usize] as usize]
+    }
+
+    /// Identifies a function type by the first type that is equal to it, as
+    /// `call_indirect` compares types structurally. Null table entries have
+    /// the signature 0.
+    fn signature(&self, type_index: u32) -> usize {
+        let ty = &self.types[type_index as usize];
+        self.types.iter().position(|other| other == ty).unwrap() + 1
+    }
+
+    fn function_label(&self, function_index: u32) -> String {
+        match self.function_names.get(&function_index) {
+            Some(name) => format!("{}_{name}", self.function_prefix),
+            None => format!("{}_{function_index}", self.function_prefix),
+        }
+    }
+}
+
+/// Name of the `VAR GLOBAL` that holds a WASM global.
+fn global_variable(global_index: u32) -> String {
+    format!("global_{global_index}")
+}
+
+/// Name of the `VAR GLOBAL` that holds the byte address of a data segment.
+fn data_segment_address(data_index: u32) -> String {
+    format!("data_{data_index}_address")
+}
+
+/// Name of the `VAR GLOBAL` that holds the length of a data segment, which is
+/// zero once the segment has been dropped.
+fn data_segment_bytes(data_index: u32) -> String {
+    format!("data_{data_index}_bytes")
+}
+
+/// Evaluates a constant expression. Floats evaluate to their bit patterns.
+fn eval_const_expr(expr: &ConstExpr) -> Result<u64> {
+    let mut reader = expr.get_operators_reader();
+    let value = match reader.read()? {
+        Operator::I32Const { value } => (value as u32).into(),
+        Operator::I64Const { value } => value as u64,
+        Operator::F32Const { value } => value.bits().into(),
+        Operator::F64Const { value } => value.bits(),
+        operator => anyhow::bail!("Unsupported constant expression: {operator:?}"),
+    };
+    if !matches!(reader.read()?, Operator::End) {
+        anyhow::bail!("Unsupported constant expression");
+    }
+    Ok(value)
+}
+
+// Calling convention.
+//
+// The caller pushes the arguments onto the operand stack and executes
+// `:CALL(function_N)`. The callee frame then starts at the first argument:
+//
+//   SP_entry - params              arguments
+//   SP_entry                       locals, zero-initialized
+//   SP_entry + locals              saved return address (RR)
+//   SP_entry + locals + 1          operand stack of the callee
+//
+// All frame slots are addressed relative to `SP` with the statically known
+// `stack_depth`, so no frame pointer is needed. On return the results are
+// copied to the start of the frame and `SP` is set right after them, which
+// leaves the results on the caller's operand stack in place of the arguments.
+struct ZkCodegenVisitor<'m> {
     assembler: ZkAssembler,
+    config: &'m Config,
+    module: &'m ModuleContext,
+    runtime: &'m mut Runtime,
     locals: Vec<Local>,
     stack_depth: i32,
+    // Values of the stack slots that are known to hold an `i32.const`.
+    stack_constants: BTreeMap<i32, i32>,
     next_block_index: u32,
     blocks: Vec<Block>,
 }
 
-impl ZkCodegenVisitor {
-    fn new(assembler: ZkAssembler, local_counts: Vec<(u32, ValType)>, params: Vec<ValType>, function_index: u32) -> Self {
-        let mut locals = Vec::new();
-        for (index, ty) in params.iter().enumerate() {
-            locals.push(Local {
-                location: Location::Stack(index as i32),
-                ty: ty.clone(),
-            });
-        }
+impl<'m> ZkCodegenVisitor<'m> {
+    fn new(
+        assembler: ZkAssembler,
+        config: &'m Config,
+        module: &'m ModuleContext,
+        runtime: &'m mut Runtime,
+        local_counts: Vec<(u32, ValType)>,
+        function_index: u32,
+        next_block_index: u32,
+    ) -> Self {
+        let func_type = module.func_type(function_index);
+        let mut local_types = func_type.params().to_vec();
         for (count, ty) in local_counts {
             for _ in 0..count {
-                locals.push(Local {
-                    location: Location::Uninitialized,
-                    ty,
-                });
+                local_types.push(ty);
             }
         }
+        let locals = local_types
+            .into_iter()
+            .enumerate()
+            .map(|(index, ty)| Local {
+                location: Location::Stack(index as i32),
+                ty,
+            })
+            .collect();
+        let params = func_type.params().len() as i32;
         let mut visitor = Self {
             assembler,
+            config,
+            module,
+            runtime,
             locals,
-            stack_depth: 0,
-            next_block_index: 1,
+            stack_depth: params,
+            stack_constants: BTreeMap::new(),
+            next_block_index,
             blocks: vec![],
         };
 
-        // Label for function start.
-        let block = Block {
-            index: function_index,
+        // Every return jumps to the epilogue at the end of the function block.
+        let mut block = Block {
+            index: visitor.next_block_index,
             block_instr: BlockInstr::Function,
+            stack_depth: 0,
+            params: 0,
+            results: func_type.results().len() as i32,
         };
-        visitor.assembler.label(&block.label());
+        visitor.next_block_index += 1;
+        // Label for function start.
+        visitor
+            .assembler
+            .label(&module.function_label(function_index));
+
+        // Prologue: allocate the non-parameter locals and save the return address.
+        for _ in params..visitor.locals.len() as i32 {
+            visitor.stack_push_const(0);
+        }
+        visitor.stack_push_register(Register::RR);
+        // The function body starts with an empty operand stack above the frame.
+        block.stack_depth = visitor.stack_depth;
         visitor.blocks.push(block);
 
         visitor
     }
 
+    /// Offset of the saved return address from the start of the frame.
+    fn return_address_offset(&self) -> i32 {
+        self.locals.len() as i32
+    }
+
+    /// Moves the top `values` values down to `stack_depth` and pops everything
+    /// above them. The compile-time `stack_depth` is left unchanged, as this is
+    /// only emitted on paths that leave the current block.
+    fn emit_stack_unwind(&mut self, stack_depth: i32, values: i32) {
+        if self.stack_depth == stack_depth + values {
+            return;
+        }
+        for index in 0..values {
+            self.assembler.stack_get(Register::E, index - values);
+            self.assembler
+                .stack_set(Register::E, stack_depth + index - self.stack_depth);
+        }
+        self.assembler
+            .stack_drop(self.stack_depth - stack_depth - values);
+    }
+
+    /// Moves the top `results` values to the start of the frame, pops the rest
+    /// of the frame and returns to the caller.
+    fn emit_epilogue(&mut self, results: i32) {
+        self.assembler.stack_get(
+            Register::RR,
+            self.return_address_offset() - self.stack_depth,
+        );
+        self.emit_stack_unwind(0, results);
+        self.assembler.ret();
+    }
+
+    /// Returns the block targeted by a branch with the given relative depth.
+    fn branch_target(&self, relative_depth: u32) -> &Block {
+        &self.blocks[self.blocks.len() - 1 - relative_depth as usize]
+    }
+
+    /// Unwinds the operand stack to the height of the target block, keeping
+    /// the values the branch carries, and jumps to it.
+    fn emit_branch(&mut self, relative_depth: u32) {
+        let block = self.branch_target(relative_depth);
+        let (stack_depth, label) = (block.stack_depth, block.label());
+        let values = block.branch_values();
+        self.emit_stack_unwind(stack_depth, values);
+        self.assembler.jump(&label);
+    }
+
+    /// Returns the number of parameters and results of a block.
+    fn block_arity(&self, blockty: BlockType) -> (i32, i32) {
+        match blockty {
+            BlockType::Empty => (0, 0),
+            BlockType::Type(_) => (0, 1),
+            BlockType::FuncType(type_index) => {
+                let ty = &self.module.types[type_index as usize];
+                (ty.params().len() as i32, ty.results().len() as i32)
+            }
+        }
+    }
+
+    fn push_block(&mut self, blockty: BlockType, block_instr: BlockInstr) -> &Block {
+        let (params, results) = self.block_arity(blockty);
+        self.blocks.push(Block {
+            index: self.next_block_index,
+            block_instr,
+            stack_depth: self.stack_depth - params,
+            params,
+            results,
+        });
+        self.next_block_index += 1;
+        self.blocks.last().unwrap()
+    }
+
+    /// Pops a memory address and puts the effective address of an access of
+    /// `bytes` bytes into `E`, trapping if the access is out of bounds.
+    fn pop_effective_address(&mut self, memarg: MemArg, bytes: u32) {
+        let address = self.stack_constants.get(&(self.stack_depth - 1)).copied();
+        self.stack_pop(Register::A);
+        if memarg.offset == 0 {
+            self.assembler.move_register(Register::A, Register::E);
+        } else {
+            self.assembler.set_const(Register::B, memarg.offset);
+            self.assembler.add(Register::E);
+        }
+
+        if let (true, Some(address), Some(memory)) = (
+            self.config.elide_constant_bounds_checks,
+            address,
+            self.module.memory,
+        ) {
+            // Memory never shrinks, so fitting into the initial memory is enough.
+            if address as u32 as u64 + memarg.offset + bytes as u64
+                <= memory.initial * WASM_PAGE_SIZE
+            {
+                return;
+            }
+        }
+        self.assembler.set_const(Register::C, bytes);
+        self.call_routine(Routine::BoundsCheck);
+    }
+
+    /// Pops a memory address and loads `bytes` bytes from it into `A`.
+    fn emit_memory_load(&mut self, memarg: MemArg, bytes: u32) {
+        self.pop_effective_address(memarg, bytes);
+        self.assembler.set_const(Register::D, byte_mask(bytes));
+        self.call_routine(Routine::Load);
+    }
+
+    /// Loads `bytes` bytes from linear memory and pushes them zero-extended.
+    fn memory_load(&mut self, memarg: MemArg, bytes: u32) {
+        self.emit_memory_load(memarg, bytes);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Loads `bytes` bytes from linear memory and pushes them sign-extended to
+    /// a `bits` wide integer.
+    fn memory_load_signed(&mut self, memarg: MemArg, bytes: u32, bits: u32) {
+        self.emit_memory_load(memarg, bytes);
+        self.emit_sign_extend(8 * bytes, bits);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Sign-extends the `from` bits wide integer in `A` to `to` bits.
+    fn emit_sign_extend(&mut self, from: u32, to: u32) {
+        let sign_bit = 1u128 << (from - 1);
+        let extension = (1u128 << to) - (1u128 << from);
+        self.assembler.set_const(Register::B, hex_literal(sign_bit));
+        self.assembler
+            .set_const(Register::C, hex_literal(extension));
+        self.call_routine(Routine::SignExtend);
+    }
+
+    /// Pops an integer and pushes its lowest `from` bits sign-extended to a
+    /// `to` bits wide integer.
+    fn emit_extend_signed(&mut self, from: u32, to: u32) {
+        self.stack_pop(Register::A);
+        self.emit_wrap(from);
+        // Fills the bits above `from` when the sign bit is set.
+        let label = format!("extend_{}", self.next_block_index);
+        self.next_block_index += 1;
+        self.assembler
+            .set_const(Register::B, hex_literal(1u128 << (from - 1)));
+        self.assembler.unsigned_less_then(Register::C);
+        self.assembler.jump_if_nonzero(Register::C, &label);
+        self.assembler
+            .set_const(Register::B, hex_literal((1u128 << to) - (1u128 << from)));
+        self.assembler.or(Register::A);
+        self.assembler.label(&label);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops a value and stores its lowest `bytes` bytes into linear memory.
+    fn memory_store(&mut self, memarg: MemArg, bytes: u32) {
+        self.stack_pop(Register::D);
+        self.pop_effective_address(memarg, bytes);
+        self.assembler.move_register(Register::D, Register::A);
+        self.assembler.set_const(Register::D, byte_mask(bytes));
+        self.call_routine(Routine::Store);
+    }
+
+    /// Reduces `A` modulo `2^bits`, dropping the carry or borrow of an
+    /// operation on `bits` wide integers.
+    fn emit_wrap(&mut self, bits: u32) {
+        self.assembler.set_const(Register::B, byte_mask(bits / 8));
+        self.assembler.and(Register::A);
+    }
+
+    /// Pops two `bits` wide integers and computes into `A` whether the first
+    /// one is less than the second one, or greater than it if `swap` is set.
+    fn emit_less_than(&mut self, bits: u32, signed: bool, swap: bool) {
+        if signed {
+            // Flipping the sign bit maps the signed range onto an ordered unsigned one.
+            let sign_bit = hex_literal(1u128 << (bits - 1));
+            self.stack_pop(Register::A);
+            self.assembler.set_const(Register::B, &sign_bit);
+            self.assembler.xor(Register::C);
+            self.stack_pop(Register::A);
+            self.assembler.xor(Register::A);
+            if swap {
+                self.assembler.move_register(Register::A, Register::B);
+                self.assembler.move_register(Register::C, Register::A);
+            } else {
+                self.assembler.move_register(Register::C, Register::B);
+            }
+        } else if swap {
+            self.stack_pop(Register::A);
+            self.stack_pop(Register::B);
+        } else {
+            self.stack_pop(Register::B);
+            self.stack_pop(Register::A);
+        }
+        self.assembler.unsigned_less_then(Register::A);
+    }
+
+    /// Negates the boolean in `A`.
+    fn emit_not(&mut self) {
+        self.assembler.set_const(Register::B, 0);
+        self.assembler.eq(Register::A);
+    }
+
+    /// Pops two `bits` wide integers and pushes their wrapped product.
+    fn emit_mul(&mut self, bits: u32) {
+        self.stack_pop(Register::B);
+        self.stack_pop(Register::A);
+        self.assembler.set_const(Register::C, 0);
+        self.assembler.set_const(Register::D, 0);
+        self.assembler.mul(Register::A);
+        self.emit_wrap(bits);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops a shift amount and a `bits` wide integer and pushes the shifted
+    /// integer. Shifts are lowered to multiplications and divisions by
+    /// `2^(amount % bits)`.
+    fn emit_shift(&mut self, bits: u32, shift: Shift) {
+        self.stack_pop(Register::A);
+        if let Shift::RotateRight = shift {
+            // Rotating right by `k` is rotating left by `bits - k`.
+            self.assembler.move_register(Register::A, Register::B);
+            self.assembler.set_const(Register::A, 0);
+            self.assembler.sub(Register::A);
+        }
+        self.assembler.set_const(Register::B, bits - 1);
+        self.assembler.and(Register::E);
+        self.call_routine(Routine::Pow2);
+        self.stack_pop(Register::A);
+        match shift {
+            Shift::Left => {
+                self.assembler.set_const(Register::C, 0);
+                self.assembler.set_const(Register::D, 0);
+                self.assembler.mul(Register::A);
+                self.emit_wrap(bits);
+            }
+            Shift::RightUnsigned => self.call_routine(Routine::DivRem),
+            Shift::RightSigned => {
+                self.assembler
+                    .set_const(Register::C, hex_literal(1u128 << (bits - 1)));
+                self.assembler.set_const(Register::D, byte_mask(bits / 8));
+                self.call_routine(Routine::ShiftRightSigned);
+            }
+            Shift::RotateLeft | Shift::RotateRight => {
+                self.assembler
+                    .set_const(Register::D, hex_literal(1u128 << bits));
+                self.call_routine(Routine::RotateLeft);
+            }
+        }
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops two `bits` wide integers and divides the first one by the second
+    /// one, leaving the quotient in `A` and the remainder in `C`. Traps if the
+    /// divisor is zero or, for `div_s`, if the quotient overflows.
+    fn emit_div_rem(&mut self, bits: u32, signed: bool, check_overflow: bool) {
+        self.stack_pop(Register::B);
+        self.stack_pop(Register::A);
+        if !signed {
+            self.call_routine(Routine::DivRem);
+            return;
+        }
+        self.assembler
+            .set_const(Register::D, hex_literal(1u128 << (bits - 1)));
+        self.call_routine(Routine::DivRemSigned);
+        if check_overflow {
+            self.runtime.require(Routine::Trap);
+            self.assembler
+                .jump_if_nonzero(Register::E, Routine::Trap.label());
+        }
+    }
+
+    /// Pops a `bits` wide integer and pushes the result of the bit counting
+    /// `routine` applied to it.
+    fn emit_bit_count(&mut self, bits: u32, routine: Routine) {
+        self.stack_pop(Register::A);
+        self.assembler.set_const(Register::D, bits);
+        self.call_routine(routine);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops a `bits` wide float and pushes it with the sign of the float below it.
+    fn emit_copysign(&mut self, bits: u32) {
+        let sign_bit = 1u128 << (bits - 1);
+        self.stack_pop(Register::A);
+        self.assembler.set_const(Register::B, hex_literal(sign_bit));
+        self.assembler.and(Register::C);
+        self.stack_pop(Register::A);
+        self.assembler
+            .set_const(Register::B, hex_literal(sign_bit - 1));
+        self.assembler.and(Register::A);
+        self.assembler.move_register(Register::C, Register::B);
+        self.assembler.or(Register::A);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops a `bits` wide float and pushes its absolute value.
+    fn emit_abs(&mut self, bits: u32) {
+        self.stack_pop(Register::A);
+        self.assembler
+            .set_const(Register::B, hex_literal((1u128 << (bits - 1)) - 1));
+        self.assembler.and(Register::A);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Pops a `bits` wide float and pushes it negated.
+    fn emit_neg(&mut self, bits: u32) {
+        self.stack_pop(Register::A);
+        self.assembler
+            .set_const(Register::B, hex_literal(1u128 << (bits - 1)));
+        self.assembler.xor(Register::A);
+        self.stack_push_register(Register::A);
+    }
+
+    /// Replaces the `params` operands of the soft-float function `name` with
+    /// its result.
+    fn call_soft_float(&mut self, name: &str, params: i32) {
+        self.runtime.require_soft_float(name);
+        self.assembler.call(&softfloat::label(name));
+        let arguments = self.stack_depth - params;
+        self.reset_stack_depth(arguments + 1, arguments);
+    }
+
+    fn call_routine(&mut self, routine: Routine) {
+        self.runtime.require(routine);
+        self.assembler.call(routine.label());
+    }
+
     fn stack_pop(&mut self, dst: Register) {
         self.assembler.stack_pop(dst);
         self.stack_depth -= 1;
@@ -234,24 +808,202 @@ impl ZkCodegenVisitor {
 
     fn stack_push_register(&mut self, src: Register) {
         self.assembler.stack_push_register(src);
+        self.stack_constants.remove(&self.stack_depth);
         self.stack_depth += 1;
     }
 
     fn stack_push_const(&mut self, value: i32) {
-        self.assembler.stack_push_const(value);
+        self.assembler.stack_push_const(value as u32);
+        self.stack_constants.insert(self.stack_depth, value);
         self.stack_depth += 1;
     }
 
-    fn finalize(self) -> String {
-        self.assembler.finalize()
+    fn stack_push_i64_const(&mut self, value: i64) {
+        self.assembler.stack_push_const(literal(value as u64));
+        self.stack_constants.remove(&self.stack_depth);
+        self.stack_depth += 1;
+    }
+
+    /// Sets the stack depth at a point where control flow merges. The values
+    /// of the slots starting at `stack_depth` may come from different paths.
+    fn reset_stack_depth(&mut self, stack_depth: i32, merged_from: i32) {
+        self.stack_depth = stack_depth;
+        self.stack_constants.split_off(&merged_from);
+    }
+
+    fn finalize(self) -> (String, u32) {
+        (self.assembler.finalize(), self.next_block_index)
     }
 }
 
+/// Returns a mask with the lowest `bytes` bytes set.
+fn byte_mask(bytes: u32) -> String {
+    let suffix = if bytes > 4 { "n" } else { "" };
+    format!("0x{}{suffix}", "FF".repeat(bytes as usize))
+}
+
+// Plain zkASM constants are limited to 32 bits, larger ones need the `n`
+// suffix.
+
+/// Formats an integer constant.
+fn literal(value: u64) -> String {
+    if value > u32::MAX as u64 {
+        format!("{value:#x}n")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats an integer constant in hexadecimal.
+fn hex_literal(value: u128) -> String {
+    if value > u32::MAX as u128 {
+        format!("{value:#x}n")
+    } else {
+        format!("{value:#x}")
+    }
+}
+
+/// Stores the table at `TABLE_BASE` in `start` and returns the code of
+/// `call_indirect`, which calls the function at the table index `E` after
+/// checking that its signature is `D`.
+///
+/// Every table entry takes two words: the signature of the function, and the
+/// position of the function in a jump table to the function labels.
+fn compile_table(
+    table: &[Option<u32>],
+    module: &ModuleContext,
+    runtime: &mut Runtime,
+    start: &mut ZkAssembler,
+) -> Result<String> {
+    let mut functions = Vec::new();
+    for (index, function) in table.iter().enumerate() {
+        // Null entries are left zeroed.
+        let Some(function) = *function else {
+            continue;
+        };
+        if module.assert_eq == Some(function) {
+            anyhow::bail!("Imported functions in tables are not supported");
+        }
+        let position = match functions.iter().position(|other| *other == function) {
+            Some(position) => position,
+            None => {
+                functions.push(function);
+                functions.len() - 1
+            }
+        };
+        let address = TABLE_BASE + 2 * index as u64;
+        let signature = module.signature(module.functions[function as usize]);
+        start.set_const(Register::E, address);
+        start.memory_set_const(signature, Register::E);
+        start.set_const(Register::E, address + 1);
+        start.memory_set_const(position, Register::E);
+    }
+
+    runtime.require(Routine::Trap);
+    let mut code = format!(
+        "\
+call_indirect:
+	E => A
+	{table_size} => B
+	$ => A :LT
+	A :JMPZ(trap)
+	E => A
+	E => B
+	$ => A :ADD
+	{TABLE_BASE:#x} => B
+	$ => E :ADD
+	$ => A :MLOAD(E)
+	D => B
+	$ => A :EQ
+	A :JMPZ(trap)
+	E + 1 => E
+	$ => E :MLOAD(E)
+	:JMP(@call_indirect_functions + E)
+call_indirect_functions:
+",
+        table_size = table.len()
+    );
+    for function in functions {
+        code += &format!("\t:JMP({})\n", module.function_label(function));
+    }
+    Ok(code)
+}
+
+/// Compiles the body of the function `function_index` of `module`.
+fn compile_function(
+    body: FunctionBody,
+    config: &Config,
+    module: &ModuleContext,
+    runtime: &mut Runtime,
+    function_index: u32,
+    next_block_index: &mut u32,
+) -> Result<String> {
+    let mut locals = Vec::new();
+    for local in body.get_locals_reader()? {
+        locals.push(local?);
+    }
+    // The bulk memory proposal also brings the table operators, which the
+    // validator can't reject on their own.
+    let mut operator_reader = body.get_operators_reader()?;
+    while !operator_reader.eof() {
+        if let Operator::TableInit { .. } | Operator::TableCopy { .. } | Operator::ElemDrop { .. } =
+            operator_reader.read()?
+        {
+            anyhow::bail!("Table operators are not supported");
+        }
+    }
+    let assembler = ZkAssembler::new();
+    let mut visitor = ZkCodegenVisitor::new(
+        assembler,
+        config,
+        module,
+        runtime,
+        locals,
+        function_index,
+        *next_block_index,
+    );
+    let mut operator_reader = body.get_operators_reader()?;
+    while !operator_reader.eof() {
+        operator_reader.visit_operator(&mut visitor)?;
+    }
+    let (code, block_index) = visitor.finalize();
+    *next_block_index = block_index;
+    Ok(code)
+}
+
 pub fn parse(module: &[u8]) -> Result<String> {
+    parse_with_config(module, &Config::default())
+}
+
+pub fn parse_with_config(module: &[u8], config: &Config) -> Result<String> {
+    Validator::new_with_features(WasmFeatures {
+        saturating_float_to_int: true,
+        // Proposals that are not supported yet.
+        reference_types: false,
+        tail_call: false,
+        simd: false,
+        ..Default::default()
+    })
+    .validate_all(module)?;
     let parser = wasmparser::Parser::new(0);
+    let mut declarations = String::new();
     let mut program = String::new();
+    // Initializes the module state and calls the start function.
+    let mut start = ZkAssembler::new();
+    start.label("start");
+    let mut start_function = None;
+    let mut memory_image = MemoryImage::default();
+    let mut next_passive_address = PASSIVE_DATA_BASE;
+    // Only modules with a data count section may use `memory.init` and
+    // `data.drop`, which need the data segment variables.
+    let mut data_segment_variables = false;
+    // Function of every table entry, `None` for null entries.
+    let mut table: Vec<Option<u32>> = Vec::new();
     let mut current_function_index = 0u32;
-    let mut types = Vec::new();
+    // Block indices are shared by all functions to keep labels unique.
+    let mut next_block_index = 1u32;
+    let mut module_context = ModuleContext::new("function");
+    let mut runtime = Runtime::default();
 
     for payload in parser.parse_all(module) {
         match payload? {
@@ -259,43 +1011,160 @@ pub fn parse(module: &[u8]) -> Result<String> {
             Version { .. } => { /* ... */ }
             TypeSection(reader) => {
                 for ty in reader {
-                    let ty = ty?;
-                    types.push(ty);
+                    match ty?.structural_type {
+                        wasmparser::StructuralType::Func(func) => module_context.types.push(func),
+                        _ => anyhow::bail!("Only function types are supported"),
+                    }
                 }
             }
             ImportSection(reader) => {
                 for import in reader.into_iter() {
                     let import = import?;
                     match import.ty {
-                        wasmparser::TypeRef::Func(_) => {
-                            // TODO: Remember which function corresponds to an ASSERT.
+                        wasmparser::TypeRef::Func(type_index) => {
+                            if (import.module, import.name) != ("env", "assert_eq") {
+                                anyhow::bail!(
+                                    "Imported function {}.{} is not supported",
+                                    import.module,
+                                    import.name
+                                );
+                            }
+                            module_context.assert_eq = Some(current_function_index);
+                            module_context.functions.push(type_index);
                             current_function_index += 1;
                         }
+                        wasmparser::TypeRef::Global(_) => anyhow::bail!(
+                            "Imported global {}.{} is not supported",
+                            import.module,
+                            import.name
+                        ),
                         _ => {}
                     }
                 }
             }
             FunctionSection(reader) => {
-                for func in reader.into_iter() {
-                    let func = func?;
-                    dbg!(func);
+                for type_index in reader.into_iter() {
+                    module_context.functions.push(type_index?);
+                }
+            }
+            TableSection(reader) => {
+                for table_type in reader {
+                    let size = table_type?.ty.initial;
+                    if u64::from(size) > MAX_TABLE_SIZE {
+                        anyhow::bail!(
+                            "Table has {size} entries, but at most {MAX_TABLE_SIZE} are allowed"
+                        );
+                    }
+                    table = vec![None; size as usize];
+                }
+            }
+            MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory?;
+                    if memory.memory64 || module_context.memory.is_some() {
+                        anyhow::bail!("Only a single 32-bit memory is supported");
+                    }
+                    let max_pages = memory
+                        .maximum
+                        .unwrap_or(WASM_MAX_PAGES)
+                        .min(config.max_memory_pages.into())
+                        .min(MAX_MEMORY_PAGES);
+                    if memory.initial > max_pages {
+                        anyhow::bail!(
+                            "Memory needs {} pages, but at most {max_pages} are allowed",
+                            memory.initial
+                        );
+                    }
+                    declarations += &format!("VAR GLOBAL {MEMORY_PAGES}\n");
+                    declarations += &format!("VAR GLOBAL {MEMORY_MAX_PAGES}\n");
+                    declarations += &format!("VAR GLOBAL {MEMORY_BYTES}\n");
+                    start.variable_set_const(memory.initial, MEMORY_PAGES);
+                    start
+                        .variable_set_const(literal(memory.initial * WASM_PAGE_SIZE), MEMORY_BYTES);
+                    start.variable_set_const(max_pages, MEMORY_MAX_PAGES);
+                    module_context.memory = Some(memory);
                 }
             }
-            TableSection(_) => { /* ... */ }
-            MemorySection(_) => { /* ... */ }
             TagSection(_) => { /* ... */ }
-            GlobalSection(_) => { /* ... */ }
+            GlobalSection(reader) => {
+                for global in reader {
+                    let global = global?;
+                    let name = global_variable(module_context.globals.len() as u32);
+                    declarations += &format!("VAR GLOBAL {name}\n");
+                    start.variable_set_const(literal(eval_const_expr(&global.init_expr)?), &name);
+                    module_context.globals.push(global.ty);
+                }
+            }
             ExportSection(_) => { /* ... */ }
             StartSection { func, .. } => {
-                let mut assembler = ZkAssembler::new();
-                assembler.label("start");
-                assembler.jump(&format!("function_{}", func));
-                program += &assembler.finalize();
-                program += "\n";
+                start_function = Some(func);
+            }
+            ElementSection(reader) => {
+                for element in reader {
+                    let element = element?;
+                    // Only `table.init` reads passive and declared segments.
+                    let wasmparser::ElementKind::Active { offset_expr, .. } = element.kind else {
+                        continue;
+                    };
+                    let offset = eval_const_expr(&offset_expr)? as u32 as usize;
+                    let functions = match element.items {
+                        wasmparser::ElementItems::Functions(reader) => reader
+                            .into_iter()
+                            .map(|function| Ok(Some(function?)))
+                            .collect::<Result<Vec<_>>>()?,
+                        wasmparser::ElementItems::Expressions(..) => {
+                            anyhow::bail!("Element expressions are not supported")
+                        }
+                    };
+                    if offset + functions.len() > table.len() {
+                        anyhow::bail!("Element segment is out of bounds of the table");
+                    }
+                    table[offset..offset + functions.len()].copy_from_slice(&functions);
+                }
+            }
+            DataCountSection { .. } => {
+                data_segment_variables = true;
+            }
+            DataSection(reader) => {
+                for (data_index, data) in reader.into_iter().enumerate() {
+                    let data = data?;
+                    let (address, bytes) = match data.kind {
+                        wasmparser::DataKind::Active { offset_expr, .. } => {
+                            let offset = eval_const_expr(&offset_expr)? as u32;
+                            let memory_size = module_context
+                                .memory
+                                .map_or(0, |memory| memory.initial * WASM_PAGE_SIZE);
+                            if offset as u64 + data.data.len() as u64 > memory_size {
+                                anyhow::bail!("Data segment is out of bounds of the memory");
+                            }
+                            memory_image.write(offset.into(), data.data);
+                            // Active segments are dropped once they are copied.
+                            (0, 0)
+                        }
+                        wasmparser::DataKind::Passive => {
+                            let address = next_passive_address;
+                            let bytes = data.data.len() as u64;
+                            memory_image.write(address, data.data);
+                            next_passive_address += bytes.next_multiple_of(32);
+                            if next_passive_address > PASSIVE_DATA_BASE + MAX_PASSIVE_DATA_BYTES {
+                                anyhow::bail!(
+                                    "Passive data segments take more than {MAX_PASSIVE_DATA_BYTES} bytes"
+                                );
+                            }
+                            (address, bytes)
+                        }
+                    };
+                    if data_segment_variables {
+                        let data_index = data_index as u32;
+                        let address_name = data_segment_address(data_index);
+                        let bytes_name = data_segment_bytes(data_index);
+                        declarations += &format!("VAR GLOBAL {address_name}\n");
+                        declarations += &format!("VAR GLOBAL {bytes_name}\n");
+                        start.variable_set_const(literal(address), &address_name);
+                        start.variable_set_const(bytes, &bytes_name);
+                    }
+                }
             }
-            ElementSection(_) => { /* ... */ }
-            DataCountSection { .. } => { /* ... */ }
-            DataSection(_) => { /* ... */ }
 
             // Here we know how many functions we'll be receiving as
             // `CodeSectionEntry`, so we can prepare for that, and
@@ -303,25 +1172,14 @@ pub fn parse(module: &[u8]) -> Result<String> {
             // individually.
             CodeSectionStart { .. } => { /* ... */ }
             CodeSectionEntry(body) => {
-                let mut locals = Vec::new();
-                for local in body.get_locals_reader()? {
-                    locals.push(local?);
-                }
-                let params: Vec<ValType> = match &types
-                    .get(current_function_index as usize)
-                    .unwrap()
-                    .structural_type
-                {
-                    wasmparser::StructuralType::Func(func) => func.params().to_vec(),
-                    _ => Vec::new(),
-                };
-                let assembler = ZkAssembler::new();
-                let mut visitor = ZkCodegenVisitor::new(assembler, locals, params, current_function_index);
-                let mut operator_reader = body.get_operators_reader()?;
-                while !operator_reader.eof() {
-                    operator_reader.visit_operator(&mut visitor)?;
-                }
-                program += &visitor.finalize();
+                program += &compile_function(
+                    body,
+                    config,
+                    &module_context,
+                    &mut runtime,
+                    current_function_index,
+                    &mut next_block_index,
+                )?;
                 program += "\n";
                 current_function_index += 1;
             }
@@ -351,6 +1209,22 @@ pub fn parse(module: &[u8]) -> Result<String> {
         }
     }
 
+    if !runtime.soft_float_functions().is_empty() {
+        program += &softfloat::compile(config, &mut runtime, &mut next_block_index)?;
+    }
+
+    if runtime.call_indirect_required() {
+        program += &compile_table(&table, &module_context, &mut runtime, &mut start)?;
+    }
+
+    memory_image.emit(&mut start);
+    if let Some(func) = start_function {
+        start.call(&module_context.function_label(func));
+    }
+    start.jump("finalizeExecution");
+
+    program = declarations + &runtime.declarations() + &start.finalize() + "\n" + &program;
+    program += &runtime.code();
     program += "\
 finalizeExecution:
 	${beforeLast()}  :JMPN(finalizeExecution)
@@ -361,8 +1235,15 @@ finalizeExecution:
 
 macro_rules! define_visit_once {
     (@mvp $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident) => {};
+    (@saturating_float_to_int $op:ident => $visit:ident) => {};
+    (@sign_extension $op:ident => $visit:ident) => {};
+    (@bulk_memory MemoryInit $($rest:tt)*) => {};
+    (@bulk_memory DataDrop $($rest:tt)*) => {};
+    (@bulk_memory MemoryCopy $($rest:tt)*) => {};
+    (@bulk_memory MemoryFill $($rest:tt)*) => {};
     (@$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident) => {
         fn $visit(&mut self $($(,$arg: $argty)*)?) {
+            $($(let _ = $arg;)*)?
             panic!("Operator not implemented");
         }
     };
@@ -378,98 +1259,182 @@ macro_rules! define_visit_operator {
     }
 }
 
-impl<'a> wasmparser::VisitOperator<'a> for ZkCodegenVisitor {
+impl<'a, 'm> wasmparser::VisitOperator<'a> for ZkCodegenVisitor<'m> {
     type Output = ();
 
     wasmparser::for_each_operator!(define_visit_operator);
 
     fn visit_unreachable(&mut self) -> Self::Output {
-        todo!()
+        self.runtime.require(Routine::Trap);
+        self.assembler.jump(Routine::Trap.label());
     }
 
     fn visit_nop(&mut self) -> Self::Output {
         todo!()
     }
 
-    fn visit_block(&mut self, _blockty: BlockType) -> Self::Output {
-        self.blocks.push(Block {
-            index: self.next_block_index,
-            block_instr: BlockInstr::Block,
-        });
-        self.next_block_index += 1;
+    fn visit_block(&mut self, blockty: BlockType) -> Self::Output {
+        self.push_block(blockty, BlockInstr::Block);
     }
 
-    fn visit_loop(&mut self, _blockty: BlockType) -> Self::Output {
-        let block = Block {
-            index: self.next_block_index,
-            block_instr: BlockInstr::Loop,
-        };
-        self.assembler.label(&block.label());
-        self.blocks.push(block);
-        self.next_block_index += 1;
+    fn visit_loop(&mut self, blockty: BlockType) -> Self::Output {
+        let block = self.push_block(blockty, BlockInstr::Loop);
+        let (label, stack_depth) = (block.label(), block.stack_depth);
+        self.assembler.label(&label);
+        // Branches back to the loop pass new values for its parameters.
+        self.reset_stack_depth(self.stack_depth, stack_depth);
     }
 
-    fn visit_if(&mut self, _blockty: BlockType) -> Self::Output {
-        todo!()
+    fn visit_if(&mut self, blockty: BlockType) -> Self::Output {
+        self.stack_pop(Register::A);
+        let else_label = self.push_block(blockty, BlockInstr::If).else_label();
+        self.assembler.jump_if_zero(Register::A, &else_label);
     }
 
     fn visit_else(&mut self) -> Self::Output {
-        todo!()
+        let block = self.blocks.last_mut().expect("No block for else");
+        block.block_instr = BlockInstr::Else;
+        self.assembler.jump(&block.label());
+        self.assembler.label(&block.else_label());
+        // The else arm starts from the same stack as the then arm did.
+        let (stack_depth, params) = (block.stack_depth, block.params);
+        self.reset_stack_depth(stack_depth + params, stack_depth);
     }
 
     fn visit_end(&mut self) -> Self::Output {
         let block = self.blocks.pop().expect("No block to pop");
-        if let BlockInstr::Block = block.block_instr {
-            self.assembler.label(&format!("block_{}", block.index))
+        match block.block_instr {
+            BlockInstr::Block | BlockInstr::Else | BlockInstr::Function => {
+                self.assembler.label(&block.label())
+            }
+            BlockInstr::If => {
+                // Without an else arm, a false condition skips straight to the end.
+                self.assembler.label(&block.else_label());
+                self.assembler.label(&block.label());
+            }
+            BlockInstr::Loop => {}
+        }
+        // Every path into the end of a block leaves its results on top of the
+        // entry stack, even if the code right before it was unreachable.
+        self.reset_stack_depth(block.stack_depth + block.results, block.stack_depth);
+        if let BlockInstr::Function = block.block_instr {
+            self.emit_epilogue(block.results);
         }
     }
 
     fn visit_br(&mut self, relative_depth: u32) -> Self::Output {
-        let block = &self.blocks[(self.next_block_index - relative_depth - 1) as usize];
-        self.assembler.jump(&block.label());
+        self.emit_branch(relative_depth);
     }
 
     fn visit_br_if(&mut self, relative_depth: u32) -> Self::Output {
         self.stack_pop(Register::A);
-        let block = &self.blocks[(self.next_block_index - relative_depth - 1) as usize];
-        self.assembler.jump_if_nonzero(Register::A, &block.label());
+        let block = self.branch_target(relative_depth);
+        if self.stack_depth == block.stack_depth + block.branch_values() {
+            // Nothing to unwind, so the branch can jump straight to the block.
+            self.assembler.jump_if_nonzero(Register::A, &block.label());
+            return;
+        }
+        // Only the path that takes the branch unwinds the stack.
+        let label = format!("br_if_{}", self.next_block_index);
+        self.next_block_index += 1;
+        self.assembler.jump_if_zero(Register::A, &label);
+        self.emit_branch(relative_depth);
+        self.assembler.label(&label);
     }
 
-    fn visit_br_table(&mut self, _targets: BrTable<'a>) -> Self::Output {
-        todo!()
+    fn visit_br_table(&mut self, targets: BrTable<'a>) -> Self::Output {
+        self.stack_pop(Register::A);
+        let table_index = self.next_block_index;
+        self.next_block_index += 1;
+        let table_label = format!("br_table_{table_index}");
+        let target_label = |relative_depth: u32| format!("{table_label}_depth_{relative_depth}");
+
+        let depths = targets.targets().collect::<Result<Vec<_>, _>>().unwrap();
+        if depths.len() < BR_TABLE_JUMP_THRESHOLD {
+            for (index, relative_depth) in depths.iter().enumerate() {
+                self.assembler.set_const(Register::B, index as i32);
+                self.assembler.eq(Register::B);
+                self.assembler
+                    .jump_if_nonzero(Register::B, &target_label(*relative_depth));
+            }
+            self.assembler.jump(&target_label(targets.default()));
+        } else {
+            // Computed jump into a table of jumps, one instruction per entry.
+            self.assembler.set_const(Register::B, depths.len() as i32);
+            self.assembler.unsigned_less_then(Register::B);
+            self.assembler
+                .jump_if_zero(Register::B, &target_label(targets.default()));
+            self.assembler.move_register(Register::A, Register::E);
+            self.assembler.jump_indexed(&table_label, Register::E);
+            self.assembler.label(&table_label);
+            for relative_depth in &depths {
+                self.assembler.jump(&target_label(*relative_depth));
+            }
+        }
+
+        // Each distinct target gets its own stub that unwinds the stack to its height.
+        let mut stubs = depths;
+        stubs.push(targets.default());
+        stubs.sort_unstable();
+        stubs.dedup();
+        for relative_depth in stubs {
+            self.assembler.label(&target_label(relative_depth));
+            self.emit_branch(relative_depth);
+        }
     }
 
     fn visit_return(&mut self) -> Self::Output {
-        todo!()
+        // The function block is the outermost one.
+        self.emit_branch(self.blocks.len() as u32 - 1);
     }
 
     fn visit_call(&mut self, function_index: u32) -> Self::Output {
-        // TODO: Replace this with a table lookup.
-        if function_index == 0 {
+        if self.module.assert_eq == Some(function_index) {
             self.stack_pop(Register::A);
             self.stack_pop(Register::B);
             self.assembler.assert(Register::B);
             return;
         }
-        // TODO: Also need to switch the stack frame to new function.
-        self.assembler.call(&format!("function_{function_index}"));
+        let func_type = self.module.func_type(function_index);
+        self.assembler
+            .call(&self.module.function_label(function_index));
+        // The callee replaces the arguments with its results.
+        let arguments = self.stack_depth - func_type.params().len() as i32;
+        self.reset_stack_depth(arguments + func_type.results().len() as i32, arguments);
     }
 
     fn visit_call_indirect(
         &mut self,
-        _type_index: u32,
+        type_index: u32,
         _table_index: u32,
         _table_byte: u8,
     ) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::E);
+        self.assembler
+            .set_const(Register::D, self.module.signature(type_index));
+        self.runtime.require_call_indirect();
+        self.assembler.call("call_indirect");
+        let func_type = &self.module.types[type_index as usize];
+        // The callee replaces the arguments with its results.
+        let arguments = self.stack_depth - func_type.params().len() as i32;
+        self.reset_stack_depth(arguments + func_type.results().len() as i32, arguments);
     }
 
     fn visit_drop(&mut self) -> Self::Output {
-        todo!()
+        self.assembler.stack_drop(1);
+        self.stack_depth -= 1;
     }
 
     fn visit_select(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::C);
+        self.stack_pop(Register::B);
+        self.stack_pop(Register::A);
+        let label = format!("select_{}", self.next_block_index);
+        self.next_block_index += 1;
+        self.assembler.jump_if_nonzero(Register::C, &label);
+        self.assembler.move_register(Register::B, Register::A);
+        self.assembler.label(&label);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_local_get(&mut self, local_index: u32) -> Self::Output {
@@ -488,9 +1453,6 @@ impl<'a> wasmparser::VisitOperator<'a> for ZkCodegenVisitor {
             Location::Register(register) => {
                 self.stack_push_register(register);
             }
-            Location::Uninitialized => {
-                panic!("Local {local_index} is not initialized");
-            }
         }
     }
 
@@ -510,144 +1472,216 @@ impl<'a> wasmparser::VisitOperator<'a> for ZkCodegenVisitor {
             Location::Register(register) => {
                 self.stack_pop(register);
             }
-            Location::Uninitialized => {
-                self.stack_pop(Register::E);
-                self.locals[local_index as usize].location = Location::Stack(self.stack_depth);
-                self.stack_push_register(Register::E);
+        }
+    }
+
+    fn visit_local_tee(&mut self, local_index: u32) -> Self::Output {
+        let location = self
+            .locals
+            .get(local_index as usize)
+            .unwrap_or_else(|| panic!("Can't find local {}", local_index))
+            .location;
+
+        match location {
+            Location::Stack(offset) => {
+                self.assembler.stack_get(Register::E, -1);
+                self.assembler
+                    .stack_set(Register::E, offset - self.stack_depth);
+            }
+            Location::Register(register) => {
+                self.assembler.stack_get(register, -1);
             }
         }
     }
 
-    fn visit_local_tee(&mut self, _local_index: u32) -> Self::Output {
-        todo!()
-    }
-
-    fn visit_global_get(&mut self, _global_index: u32) -> Self::Output {
-        todo!()
+    fn visit_global_get(&mut self, global_index: u32) -> Self::Output {
+        self.assembler
+            .variable_get(Register::A, &global_variable(global_index));
+        self.stack_push_register(Register::A);
     }
 
-    fn visit_global_set(&mut self, _global_index: u32) -> Self::Output {
-        todo!()
+    fn visit_global_set(&mut self, global_index: u32) -> Self::Output {
+        let global = self
+            .module
+            .globals
+            .get(global_index as usize)
+            .unwrap_or_else(|| panic!("Can't find global {global_index}"));
+        if !global.mutable {
+            panic!("Global {global_index} is immutable");
+        }
+        self.stack_pop(Register::A);
+        self.assembler
+            .variable_set(Register::A, &global_variable(global_index));
     }
 
-    fn visit_i32_load(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_load(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 4);
     }
 
-    fn visit_i64_load(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 8);
     }
 
-    fn visit_f32_load(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_f32_load(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 4);
     }
 
-    fn visit_f64_load(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_f64_load(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 8);
     }
 
-    fn visit_i32_load8_s(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_load8_s(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load_signed(memarg, 1, 32);
     }
 
-    fn visit_i32_load8_u(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_load8_u(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 1);
     }
 
-    fn visit_i32_load16_s(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_load16_s(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load_signed(memarg, 2, 32);
     }
 
-    fn visit_i32_load16_u(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_load16_u(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 2);
     }
 
-    fn visit_i64_load8_s(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load8_s(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load_signed(memarg, 1, 64);
     }
 
-    fn visit_i64_load8_u(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load8_u(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 1);
     }
 
-    fn visit_i64_load16_s(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load16_s(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load_signed(memarg, 2, 64);
     }
 
-    fn visit_i64_load16_u(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load16_u(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 2);
     }
 
-    fn visit_i64_load32_s(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load32_s(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load_signed(memarg, 4, 64);
     }
 
-    fn visit_i64_load32_u(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_load32_u(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_load(memarg, 4);
     }
 
-    fn visit_i32_store(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_store(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 4);
     }
 
-    fn visit_i64_store(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_store(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 8);
     }
 
-    fn visit_f32_store(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_f32_store(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 4);
     }
 
-    fn visit_f64_store(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_f64_store(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 8);
     }
 
-    fn visit_i32_store8(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_store8(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 1);
     }
 
-    fn visit_i32_store16(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i32_store16(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 2);
     }
 
-    fn visit_i64_store8(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_store8(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 1);
     }
 
-    fn visit_i64_store16(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_store16(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 2);
     }
 
-    fn visit_i64_store32(&mut self, _mamarg: MemArg) -> Self::Output {
-        todo!()
+    fn visit_i64_store32(&mut self, memarg: MemArg) -> Self::Output {
+        self.memory_store(memarg, 4);
     }
 
     fn visit_memory_size(&mut self, _mem: u32, _mem_byte: u8) -> Self::Output {
-        todo!()
+        self.assembler.variable_get(Register::A, MEMORY_PAGES);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_memory_grow(&mut self, _mem: u32, _mem_byte: u8) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.call_routine(Routine::MemoryGrow);
+        self.stack_push_register(Register::A);
+    }
+
+    fn visit_memory_init(&mut self, data_index: u32, _mem: u32) -> Self::Output {
+        self.stack_pop(Register::C);
+        self.stack_pop(Register::A);
+        // Traps unless the segment still has the bytes `A..A + C`.
+        self.assembler.move_register(Register::A, Register::D);
+        self.assembler.move_register(Register::C, Register::B);
+        self.assembler.add(Register::B);
+        self.assembler
+            .variable_get(Register::A, &data_segment_bytes(data_index));
+        self.assembler.unsigned_less_then(Register::A);
+        self.runtime.require(Routine::Trap);
+        self.assembler
+            .jump_if_nonzero(Register::A, Routine::Trap.label());
+        self.assembler
+            .variable_get(Register::A, &data_segment_address(data_index));
+        self.assembler.move_register(Register::D, Register::B);
+        self.assembler.add(Register::D);
+        self.stack_pop(Register::E);
+        self.call_routine(Routine::BoundsCheck);
+        self.call_routine(Routine::MemoryCopy);
+    }
+
+    fn visit_data_drop(&mut self, data_index: u32) -> Self::Output {
+        self.assembler
+            .variable_set_const(0, &data_segment_bytes(data_index));
+    }
+
+    fn visit_memory_copy(&mut self, _dst_mem: u32, _src_mem: u32) -> Self::Output {
+        self.stack_pop(Register::C);
+        self.stack_pop(Register::E);
+        self.call_routine(Routine::BoundsCheck);
+        self.assembler.move_register(Register::E, Register::D);
+        self.stack_pop(Register::E);
+        self.call_routine(Routine::BoundsCheck);
+        self.call_routine(Routine::MemoryCopy);
+    }
+
+    fn visit_memory_fill(&mut self, _mem: u32) -> Self::Output {
+        self.stack_pop(Register::C);
+        self.stack_pop(Register::D);
+        self.stack_pop(Register::E);
+        self.call_routine(Routine::BoundsCheck);
+        self.call_routine(Routine::MemoryFill);
     }
 
     fn visit_i32_const(&mut self, value: i32) -> Self::Output {
         self.stack_push_const(value);
     }
 
-    fn visit_i64_const(&mut self, _value: i64) -> Self::Output {
-        todo!()
+    fn visit_i64_const(&mut self, value: i64) -> Self::Output {
+        self.stack_push_i64_const(value);
     }
 
-    fn visit_f32_const(&mut self, _value: Ieee32) -> Self::Output {
-        todo!()
+    fn visit_f32_const(&mut self, value: Ieee32) -> Self::Output {
+        self.stack_push_i64_const(value.bits().into());
     }
 
-    fn visit_f64_const(&mut self, _value: Ieee64) -> Self::Output {
-        todo!()
+    fn visit_f64_const(&mut self, value: Ieee64) -> Self::Output {
+        self.stack_push_i64_const(value.bits() as i64);
     }
 
     fn visit_i32_eqz(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_eq(&mut self) -> Self::Output {
@@ -658,183 +1692,220 @@ impl<'a> wasmparser::VisitOperator<'a> for ZkCodegenVisitor {
     }
 
     fn visit_i32_ne(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.eq(Register::A);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_lt_s(&mut self) -> Self::Output {
-        self.stack_pop(Register::A);
-        self.stack_pop(Register::B);
-        self.assembler.signed_less_then(Register::A);
+        self.emit_less_than(I32_BITS, true, false);
         self.stack_push_register(Register::A);
     }
 
     fn visit_i32_lt_u(&mut self) -> Self::Output {
-        self.stack_pop(Register::A);
-        self.stack_pop(Register::B);
-        self.assembler.unsigned_less_then(Register::A);
+        self.emit_less_than(I32_BITS, false, false);
         self.stack_push_register(Register::A);
     }
 
     fn visit_i32_gt_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, true, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_gt_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, false, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_le_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, true, true);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_le_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, false, true);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_ge_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, true, false);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_ge_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I32_BITS, false, false);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_eqz(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_eq(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.eq(Register::A);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_ne(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.eq(Register::A);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_lt_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, true, false);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_lt_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, false, false);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_gt_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, true, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_gt_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, false, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_le_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, true, true);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_le_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, false, true);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_ge_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, true, false);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_ge_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_less_than(I64_BITS, false, false);
+        self.emit_not();
+        self.stack_push_register(Register::A);
     }
 
     fn visit_f32_eq(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_eq", 2);
     }
 
     fn visit_f32_ne(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_ne", 2);
     }
 
     fn visit_f32_lt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_lt", 2);
     }
 
     fn visit_f32_gt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_gt", 2);
     }
 
     fn visit_f32_le(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_le", 2);
     }
 
     fn visit_f32_ge(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_ge", 2);
     }
 
     fn visit_f64_eq(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_eq", 2);
     }
 
     fn visit_f64_ne(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_ne", 2);
     }
 
     fn visit_f64_lt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_lt", 2);
     }
 
     fn visit_f64_gt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_gt", 2);
     }
 
     fn visit_f64_le(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_le", 2);
     }
 
     fn visit_f64_ge(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_ge", 2);
     }
 
     fn visit_i32_clz(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I32_BITS, Routine::Clz);
     }
 
     fn visit_i32_ctz(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I32_BITS, Routine::Ctz);
     }
 
     fn visit_i32_popcnt(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I32_BITS, Routine::Popcnt);
     }
 
     fn visit_i32_add(&mut self) -> Self::Output {
         self.stack_pop(Register::A);
         self.stack_pop(Register::B);
         self.assembler.add(Register::A);
+        self.emit_wrap(I32_BITS);
         self.stack_push_register(Register::A);
     }
 
     fn visit_i32_sub(&mut self) -> Self::Output {
-        self.stack_pop(Register::A);
         self.stack_pop(Register::B);
+        self.stack_pop(Register::A);
         self.assembler.sub(Register::A);
+        self.emit_wrap(I32_BITS);
         self.stack_push_register(Register::A);
     }
 
     fn visit_i32_mul(&mut self) -> Self::Output {
-        todo!()
+        self.emit_mul(I32_BITS);
     }
 
     fn visit_i32_div_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I32_BITS, true, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_div_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I32_BITS, false, false);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_rem_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I32_BITS, true, false);
+        self.stack_push_register(Register::C);
     }
 
     fn visit_i32_rem_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I32_BITS, false, false);
+        self.stack_push_register(Register::C);
     }
 
     fn visit_i32_and(&mut self) -> Self::Output {
@@ -859,306 +1930,383 @@ impl<'a> wasmparser::VisitOperator<'a> for ZkCodegenVisitor {
     }
 
     fn visit_i32_shl(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I32_BITS, Shift::Left);
     }
 
     fn visit_i32_shr_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I32_BITS, Shift::RightSigned);
     }
 
     fn visit_i32_shr_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I32_BITS, Shift::RightUnsigned);
     }
 
     fn visit_i32_rotl(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I32_BITS, Shift::RotateLeft);
     }
 
     fn visit_i32_rotr(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I32_BITS, Shift::RotateRight);
     }
 
     fn visit_i64_clz(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I64_BITS, Routine::Clz);
     }
 
     fn visit_i64_ctz(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I64_BITS, Routine::Ctz);
     }
 
     fn visit_i64_popcnt(&mut self) -> Self::Output {
-        todo!()
+        self.emit_bit_count(I64_BITS, Routine::Popcnt);
     }
 
     fn visit_i64_add(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.add(Register::A);
+        self.emit_wrap(I64_BITS);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_sub(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::B);
+        self.stack_pop(Register::A);
+        self.assembler.sub(Register::A);
+        self.emit_wrap(I64_BITS);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_mul(&mut self) -> Self::Output {
-        todo!()
+        self.emit_mul(I64_BITS);
     }
 
     fn visit_i64_div_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I64_BITS, true, true);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_div_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I64_BITS, false, false);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_rem_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I64_BITS, true, false);
+        self.stack_push_register(Register::C);
     }
 
     fn visit_i64_rem_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_div_rem(I64_BITS, false, false);
+        self.stack_push_register(Register::C);
     }
 
     fn visit_i64_and(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.and(Register::A);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_or(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.or(Register::A);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_xor(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.stack_pop(Register::B);
+        self.assembler.xor(Register::A);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_shl(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I64_BITS, Shift::Left);
     }
 
     fn visit_i64_shr_s(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I64_BITS, Shift::RightSigned);
     }
 
     fn visit_i64_shr_u(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I64_BITS, Shift::RightUnsigned);
     }
 
     fn visit_i64_rotl(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I64_BITS, Shift::RotateLeft);
     }
 
     fn visit_i64_rotr(&mut self) -> Self::Output {
-        todo!()
+        self.emit_shift(I64_BITS, Shift::RotateRight);
     }
 
     fn visit_f32_abs(&mut self) -> Self::Output {
-        todo!()
+        self.emit_abs(F32_BITS);
     }
 
     fn visit_f32_neg(&mut self) -> Self::Output {
-        todo!()
+        self.emit_neg(F32_BITS);
     }
 
     fn visit_f32_ceil(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_ceil", 1);
     }
 
     fn visit_f32_floor(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_floor", 1);
     }
 
     fn visit_f32_trunc(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_trunc", 1);
     }
 
     fn visit_f32_nearest(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_nearest", 1);
     }
 
     fn visit_f32_sqrt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_sqrt", 1);
     }
 
     fn visit_f32_add(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_add", 2);
     }
 
     fn visit_f32_sub(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_sub", 2);
     }
 
     fn visit_f32_mul(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_mul", 2);
     }
 
     fn visit_f32_div(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_div", 2);
     }
 
     fn visit_f32_min(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_min", 2);
     }
 
     fn visit_f32_max(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_max", 2);
     }
 
     fn visit_f32_copysign(&mut self) -> Self::Output {
-        todo!()
+        self.emit_copysign(F32_BITS);
     }
 
     fn visit_f64_abs(&mut self) -> Self::Output {
-        todo!()
+        self.emit_abs(F64_BITS);
     }
 
     fn visit_f64_neg(&mut self) -> Self::Output {
-        todo!()
+        self.emit_neg(F64_BITS);
     }
 
     fn visit_f64_ceil(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_ceil", 1);
     }
 
     fn visit_f64_floor(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_floor", 1);
     }
 
     fn visit_f64_trunc(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_trunc", 1);
     }
 
     fn visit_f64_nearest(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_nearest", 1);
     }
 
     fn visit_f64_sqrt(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_sqrt", 1);
     }
 
     fn visit_f64_add(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_add", 2);
     }
 
     fn visit_f64_sub(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_sub", 2);
     }
 
     fn visit_f64_mul(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_mul", 2);
     }
 
     fn visit_f64_div(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_div", 2);
     }
 
     fn visit_f64_min(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_min", 2);
     }
 
     fn visit_f64_max(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_max", 2);
     }
 
     fn visit_f64_copysign(&mut self) -> Self::Output {
-        todo!()
+        self.emit_copysign(F64_BITS);
     }
 
     fn visit_i32_wrap_i64(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.emit_wrap(I32_BITS);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i32_trunc_f32_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i32_trunc_f32_s", 1);
     }
 
     fn visit_i32_trunc_f32_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i32_trunc_f32_u", 1);
     }
 
     fn visit_i32_trunc_f64_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i32_trunc_f64_s", 1);
     }
 
     fn visit_i32_trunc_f64_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i32_trunc_f64_u", 1);
     }
 
     fn visit_i64_extend_i32_s(&mut self) -> Self::Output {
-        todo!()
+        self.stack_pop(Register::A);
+        self.emit_sign_extend(I32_BITS, I64_BITS);
+        self.stack_push_register(Register::A);
     }
 
     fn visit_i64_extend_i32_u(&mut self) -> Self::Output {
-        todo!()
+        // An `i32` already is its zero extension.
     }
 
     fn visit_i64_trunc_f32_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i64_trunc_f32_s", 1);
     }
 
     fn visit_i64_trunc_f32_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i64_trunc_f32_u", 1);
     }
 
     fn visit_i64_trunc_f64_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i64_trunc_f64_s", 1);
     }
 
     fn visit_i64_trunc_f64_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("i64_trunc_f64_u", 1);
     }
 
     fn visit_f32_convert_i32_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_convert_i32_s", 1);
     }
 
     fn visit_f32_convert_i32_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_convert_i32_u", 1);
     }
 
     fn visit_f32_convert_i64_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_convert_i64_s", 1);
     }
 
     fn visit_f32_convert_i64_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_convert_i64_u", 1);
     }
 
     fn visit_f32_demote_f64(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f32_demote_f64", 1);
     }
 
     fn visit_f64_convert_i32_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_convert_i32_s", 1);
     }
 
     fn visit_f64_convert_i32_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_convert_i32_u", 1);
     }
 
     fn visit_f64_convert_i64_s(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_convert_i64_s", 1);
     }
 
     fn visit_f64_convert_i64_u(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_convert_i64_u", 1);
     }
 
     fn visit_f64_promote_f32(&mut self) -> Self::Output {
-        todo!()
+        self.call_soft_float("f64_promote_f32", 1);
     }
 
     fn visit_i32_reinterpret_f32(&mut self) -> Self::Output {
-        todo!()
+        // Floats already are their bit patterns.
     }
 
     fn visit_i64_reinterpret_f64(&mut self) -> Self::Output {
-        todo!()
+        // Floats already are their bit patterns.
     }
 
     fn visit_f32_reinterpret_i32(&mut self) -> Self::Output {
-        todo!()
+        // Floats already are their bit patterns.
     }
 
     fn visit_f64_reinterpret_i64(&mut self) -> Self::Output {
-        todo!()
+        // Floats already are their bit patterns.
+    }
+
+    fn visit_i32_trunc_sat_f32_s(&mut self) -> Self::Output {
+        self.call_soft_float("i32_trunc_sat_f32_s", 1);
+    }
+
+    fn visit_i32_trunc_sat_f32_u(&mut self) -> Self::Output {
+        self.call_soft_float("i32_trunc_sat_f32_u", 1);
+    }
+
+    fn visit_i32_trunc_sat_f64_s(&mut self) -> Self::Output {
+        self.call_soft_float("i32_trunc_sat_f64_s", 1);
+    }
+
+    fn visit_i32_trunc_sat_f64_u(&mut self) -> Self::Output {
+        self.call_soft_float("i32_trunc_sat_f64_u", 1);
+    }
+
+    fn visit_i64_trunc_sat_f32_s(&mut self) -> Self::Output {
+        self.call_soft_float("i64_trunc_sat_f32_s", 1);
+    }
+
+    fn visit_i64_trunc_sat_f32_u(&mut self) -> Self::Output {
+        self.call_soft_float("i64_trunc_sat_f32_u", 1);
+    }
+
+    fn visit_i64_trunc_sat_f64_s(&mut self) -> Self::Output {
+        self.call_soft_float("i64_trunc_sat_f64_s", 1);
+    }
+
+    fn visit_i64_trunc_sat_f64_u(&mut self) -> Self::Output {
+        self.call_soft_float("i64_trunc_sat_f64_u", 1);
+    }
+
+    fn visit_i32_extend8_s(&mut self) -> Self::Output {
+        self.emit_extend_signed(8, I32_BITS);
+    }
+
+    fn visit_i32_extend16_s(&mut self) -> Self::Output {
+        self.emit_extend_signed(16, I32_BITS);
+    }
+
+    fn visit_i64_extend8_s(&mut self) -> Self::Output {
+        self.emit_extend_signed(8, I64_BITS);
+    }
+
+    fn visit_i64_extend16_s(&mut self) -> Self::Output {
+        self.emit_extend_signed(16, I64_BITS);
+    }
+
+    fn visit_i64_extend32_s(&mut self) -> Self::Output {
+        self.emit_extend_signed(32, I64_BITS);
     }
 }
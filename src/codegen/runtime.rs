@@ -0,0 +1,887 @@
+//! Runtime routines shared by the generated code.
+//!
+//! Routines are emitted once per program, after the compiled functions, and
+//! invoked with `:CALL`. They take their arguments and return their results in
+//! registers and may clobber any of `A`-`E`. Routines that call other routines
+//! keep their return address in a `VAR GLOBAL`, so they are not reentrant.
+//!
+//! # Linear memory
+//!
+//! WASM linear memory is byte-addressed and little-endian, while zkASM memory
+//! consists of 256-bit words. Linear memory is packed 32 bytes per word: byte
+//! `a` lives in bits `8 * (a % 32)..8 * (a % 32) + 8` of the word at address
+//! `LINEAR_MEMORY_BASE + a / 32`. An access that is not contained in a single
+//! word is split between two consecutive words.
+//!
+//! `MLOAD(E)` and `MSTORE(E)` only reach the words `0..0x10000` of the
+//! executor, which never alias the operand stack (`0x10000 + SP`) or the
+//! global variables declared with `VAR GLOBAL`. Linear memory, passive data
+//! segments and the table all have to fit into that window:
+//!
+//! | Words             | Contents                                  |
+//! |-------------------|-------------------------------------------|
+//! | `0x0000..0xe000`  | Linear memory, at most `MAX_MEMORY_PAGES` |
+//! | `0xe000..0xf000`  | Passive data segments, 128 KiB            |
+//! | `0xf000..0x10000` | Table, at most `MAX_TABLE_SIZE` entries   |
+//!
+//! Modules that need more are rejected by the compiler.
+//!
+//! The current size of the memory is kept in `memory_pages` and, in bytes, in
+//! `memory_bytes`. The most pages the memory may grow to are kept in
+//! `memory_max_pages`. Every access is checked against `memory_bytes` and
+//! jumps to `trap` if any of its bytes is out of bounds.
+//!
+//! `memory.fill` and `memory.copy` move a whole word per iteration while both
+//! of their addresses are aligned to a word and at least 32 bytes are left,
+//! and a single byte otherwise. Copies run backwards when the destination is
+//! above the source, so overlapping ranges behave like `memmove`. Passive data
+//! segments live in the same packed layout starting at the byte address
+//! `PASSIVE_DATA_BASE`, right after the largest linear memory, and
+//! `memory.init` copies from there.
+//!
+//! # Bit counting
+//!
+//! `clz`, `ctz` and `popcnt` split their operand into bytes, one byte per
+//! iteration, with the quotient and remainder as free inputs that are checked
+//! with `ARITH`. The count for each byte is looked up in a 256 entry table.
+//! Every byte costs at most 30 steps, so an `i32` takes at most 125 steps and
+//! an `i64` at most 245.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Register, ZkAssembler};
+
+// Words below this address are reachable by `MLOAD(E)` and `MSTORE(E)`.
+const ADDRESSABLE_WORDS: u64 = 0x10000;
+
+/// zkASM address of the word that holds the first 32 bytes of linear memory.
+pub(super) const LINEAR_MEMORY_BASE: u32 = 0;
+
+/// The most pages linear memory can have in the addressable words.
+pub(super) const MAX_MEMORY_PAGES: u64 = 28;
+
+/// Byte address of the first passive data segment, right after the largest
+/// linear memory. Every segment starts at a word boundary.
+pub(super) const PASSIVE_DATA_BASE: u64 = MAX_MEMORY_PAGES * 65536;
+
+/// The most bytes the passive data segments can take together.
+pub(super) const MAX_PASSIVE_DATA_BYTES: u64 = 1 << 17;
+
+/// zkASM address of the first word of the table, after the passive data
+/// segments.
+pub(super) const TABLE_BASE: u64 =
+    LINEAR_MEMORY_BASE as u64 + (PASSIVE_DATA_BASE + MAX_PASSIVE_DATA_BYTES) / 32;
+
+/// The most entries the table can have, two words each.
+pub(super) const MAX_TABLE_SIZE: u64 = (ADDRESSABLE_WORDS - TABLE_BASE) / 2;
+
+pub(super) const MEMORY_PAGES: &str = "memory_pages";
+pub(super) const MEMORY_MAX_PAGES: &str = "memory_max_pages";
+pub(super) const MEMORY_BYTES: &str = "memory_bytes";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Routine {
+    /// `B = 256^E` for `E` in `0..32`.
+    Pow256,
+    /// `B = 2^E` for `E` in `0..64`.
+    Pow2,
+    /// Splits `A` at byte `E`: `A = A >> 8E`, `C = A % 256^E`, `B = 256^E`.
+    Split,
+    /// Converts the byte address `E` into the address of its word `E` and the
+    /// byte offset `C` inside of that word.
+    Locate,
+    /// `A = ` the bytes selected by the mask `D` starting at the byte address `E`.
+    Load,
+    /// Stores the bytes of `A` selected by the mask `D` at the byte address `E`.
+    Store,
+    /// Sign-extends `A`: adds the extension bits `C` if `A` is not below the
+    /// sign bit `B`.
+    SignExtend,
+    /// Grows the memory by `A` pages, returning the old size in pages or -1
+    /// if the memory can't grow that much.
+    MemoryGrow,
+    /// Traps unless the `C` bytes starting at the byte address `E` are all
+    /// inside of the memory. Preserves `C`, `D` and `E`.
+    BoundsCheck,
+    /// Sets the `C` bytes starting at the byte address `E` to the lowest byte
+    /// of `D`.
+    MemoryFill,
+    /// Copies `C` bytes from the byte address `D` to the byte address `E`.
+    MemoryCopy,
+    /// Shared target of all the jumps taken when execution traps.
+    Trap,
+    /// `A = A / B`, `C = A % B` for unsigned `A` and `B`. Traps if `B` is zero.
+    DivRem,
+    /// `A = A / B`, `C = A % B` for `A` and `B` in two's complement with the
+    /// sign bit `D`, rounding towards zero. Sets `E` to 1 if the quotient
+    /// overflows and to 0 otherwise. Traps if `B` is zero.
+    DivRemSigned,
+    /// `A = A / B` rounded towards negative infinity for `A` in two's
+    /// complement with the sign bit `C` and the mask of all its bits `D`.
+    ShiftRightSigned,
+    /// Rotates `A` left, given `B = 2^k` and `D = 2^bits`.
+    RotateLeft,
+    /// Splits off the lowest byte of `A`: `A = A / 256`, `E = A % 256`.
+    NextByte,
+    /// `B = ` the number of leading zeros of the byte `E`.
+    Clz8,
+    /// `B = ` the number of trailing zeros of the byte `E`.
+    Ctz8,
+    /// `B = ` the number of ones in the byte `E`.
+    Popcnt8,
+    /// `A = ` the number of leading zeros of the `D` bits wide `A`.
+    Clz,
+    /// `A = ` the number of trailing zeros of the `D` bits wide `A`.
+    Ctz,
+    /// `A = ` the number of ones in the `D` bits wide `A`.
+    Popcnt,
+}
+
+impl Routine {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Routine::Pow256 => "mem_pow256",
+            Routine::Pow2 => "pow2",
+            Routine::Split => "mem_split",
+            Routine::Locate => "mem_locate",
+            Routine::Load => "mem_load",
+            Routine::Store => "mem_store",
+            Routine::SignExtend => "sign_extend",
+            Routine::MemoryGrow => "memory_grow",
+            Routine::BoundsCheck => "mem_bounds_check",
+            Routine::MemoryFill => "mem_fill",
+            Routine::MemoryCopy => "mem_copy",
+            Routine::Trap => "trap",
+            Routine::DivRem => "div_rem",
+            Routine::DivRemSigned => "div_rem_s",
+            Routine::ShiftRightSigned => "shr_s",
+            Routine::RotateLeft => "rotl",
+            Routine::NextByte => "next_byte",
+            Routine::Clz8 => "clz8",
+            Routine::Ctz8 => "ctz8",
+            Routine::Popcnt8 => "popcnt8",
+            Routine::Clz => "clz",
+            Routine::Ctz => "ctz",
+            Routine::Popcnt => "popcnt",
+        }
+    }
+
+    fn dependencies(self) -> &'static [Routine] {
+        match self {
+            Routine::Pow256
+            | Routine::Pow2
+            | Routine::Locate
+            | Routine::SignExtend
+            | Routine::MemoryGrow
+            | Routine::Trap
+            | Routine::NextByte
+            | Routine::Clz8
+            | Routine::Ctz8
+            | Routine::Popcnt8 => &[],
+            Routine::Split => &[Routine::Pow256],
+            Routine::BoundsCheck | Routine::DivRem => &[Routine::Trap],
+            Routine::DivRemSigned | Routine::ShiftRightSigned | Routine::RotateLeft => {
+                &[Routine::DivRem]
+            }
+            Routine::Load | Routine::Store => &[Routine::Locate, Routine::Split, Routine::Pow256],
+            Routine::MemoryFill => &[Routine::Locate, Routine::Store],
+            Routine::MemoryCopy => &[Routine::Locate, Routine::Load, Routine::Store],
+            Routine::Clz => &[Routine::NextByte, Routine::Clz8],
+            Routine::Ctz => &[Routine::NextByte, Routine::Ctz8],
+            Routine::Popcnt => &[Routine::NextByte, Routine::Popcnt8],
+        }
+    }
+
+    fn variables(self) -> &'static [&'static str] {
+        match self {
+            Routine::Pow256
+            | Routine::Pow2
+            | Routine::Locate
+            | Routine::SignExtend
+            | Routine::MemoryGrow
+            | Routine::BoundsCheck
+            | Routine::Trap
+            | Routine::DivRem
+            | Routine::NextByte
+            | Routine::Clz8
+            | Routine::Ctz8
+            | Routine::Popcnt8 => &[],
+            Routine::Split => &["mem_split_rr"],
+            Routine::DivRemSigned => &[
+                "div_rr",
+                "div_sign_bit",
+                "div_lhs",
+                "div_rhs",
+                "div_abs_lhs",
+                "div_quotient",
+                "div_remainder",
+            ],
+            Routine::ShiftRightSigned => &["shr_s_rr", "shr_s_mask", "shr_s_positive"],
+            Routine::RotateLeft => &["rotl_rr"],
+            Routine::Clz => &["bits_rr", "bits_offset", "bits_result", "bits_value"],
+            Routine::Ctz => &["bits_rr", "bits_offset", "bits_width"],
+            Routine::Popcnt => &["bits_rr", "bits_offset", "bits_result", "bits_value"],
+            Routine::Load => &["mem_rr", "mem_mask", "mem_word", "mem_shift", "mem_value"],
+            Routine::Store => &[
+                "mem_rr",
+                "mem_mask",
+                "mem_word",
+                "mem_value",
+                "mem_low",
+                "mem_pow",
+            ],
+            Routine::MemoryFill => &["bulk_rr", "bulk_dest", "bulk_len", "bulk_value"],
+            Routine::MemoryCopy => &[
+                "bulk_rr",
+                "bulk_dest",
+                "bulk_src",
+                "bulk_len",
+                "bulk_step",
+                "bulk_backward",
+                "bulk_value",
+            ],
+        }
+    }
+
+    fn code(self) -> String {
+        match self {
+            Routine::Pow256 => {
+                let mut code =
+                    "mem_pow256:\n\t:JMP(@mem_pow256_table + E)\nmem_pow256_table:\n".to_string();
+                for exponent in 0..32 {
+                    code += &format!("\t0x1{}n => B :RETURN\n", "00".repeat(exponent));
+                }
+                code
+            }
+            Routine::Pow2 => {
+                let mut code = "pow2:\n\t:JMP(@pow2_table + E)\npow2_table:\n".to_string();
+                for exponent in 0..64 {
+                    code += &format!("\t{:#x}n => B :RETURN\n", 1u64 << exponent);
+                }
+                code
+            }
+            Routine::Clz8 => byte_table("clz8", |byte| byte.leading_zeros()),
+            Routine::Ctz8 => byte_table("ctz8", |byte| byte.trailing_zeros()),
+            Routine::Popcnt8 => byte_table("popcnt8", |byte| byte.count_ones()),
+            Routine::Split => "\
+mem_split:
+	RR :MSTORE(mem_split_rr)
+	:CALL(mem_pow256)
+	A => E
+	${E / B} => A
+	${E % B} => C
+	0 => D
+	E :ARITH
+	A => D
+	C => A
+	$ => A :LT
+	1 :ASSERT
+	D => A
+	$ => RR :MLOAD(mem_split_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::Locate => format!(
+                "\
+mem_locate:
+	${{E / 32}} => A
+	32 => B
+	${{E % 32}} => C
+	0 => D
+	E :ARITH
+	A => E
+	C => A
+	$ => A :LT
+	1 :ASSERT
+	E + {LINEAR_MEMORY_BASE} => E
+	:RETURN
+"
+            ),
+            Routine::Load => "\
+mem_load:
+	RR :MSTORE(mem_rr)
+	D :MSTORE(mem_mask)
+	:CALL(mem_locate)
+	$ => A :MLOAD(E)
+	C :JMPZ(mem_load_mask)
+	E :MSTORE(mem_word)
+	C :MSTORE(mem_shift)
+	C => E
+	:CALL(mem_split)
+	A :MSTORE(mem_value)
+	32 => A
+	$ => B :MLOAD(mem_shift)
+	$ => E :SUB
+	:CALL(mem_pow256)
+	$ => E :MLOAD(mem_word)
+	E + 1 => E
+	$ => A :MLOAD(E)
+	$ => C :MLOAD(mem_value)
+	${(A * B + C) >> 256} => D
+	${A * B + C - (D << 256)} => A :ARITH
+mem_load_mask:
+	$ => B :MLOAD(mem_mask)
+	$ => A :AND
+	$ => RR :MLOAD(mem_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::Store => "\
+mem_store:
+	RR :MSTORE(mem_rr)
+	D :MSTORE(mem_mask)
+	D => B
+	$ => A :AND
+	A :MSTORE(mem_value)
+	:CALL(mem_locate)
+	E :MSTORE(mem_word)
+	$ => A :MLOAD(E)
+	C => E
+	:CALL(mem_split)
+	C :MSTORE(mem_low)
+	B :MSTORE(mem_pow)
+	A => D
+	$ => B :MLOAD(mem_mask)
+	$ => A :AND
+	A => B
+	D => A
+	$ => A :SUB
+	$ => B :MLOAD(mem_value)
+	$ => A :ADD
+	$ => B :MLOAD(mem_pow)
+	$ => C :MLOAD(mem_low)
+	${(A * B + C) >> 256} => D
+	${A * B + C - (D << 256)} => A :ARITH
+	$ => E :MLOAD(mem_word)
+	A :MSTORE(E)
+	D :MSTORE(mem_value)
+	$ => A :MLOAD(mem_mask)
+	$ => B :MLOAD(mem_pow)
+	0 => C
+	${(A * B) >> 256} => D
+	${A * B - (D << 256)} => E :ARITH
+	D :JMPZ(mem_store_done)
+	$ => E :MLOAD(mem_word)
+	E + 1 => E
+	$ => A :MLOAD(E)
+	D => B
+	$ => B :AND
+	$ => A :SUB
+	$ => B :MLOAD(mem_value)
+	$ => A :ADD
+	A :MSTORE(E)
+mem_store_done:
+	$ => RR :MLOAD(mem_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::SignExtend => "\
+sign_extend:
+	$ => D :LT
+	D :JMPNZ(sign_extend_done)
+	C => B
+	$ => A :OR
+sign_extend_done:
+	:RETURN
+"
+            .to_string(),
+            Routine::MemoryGrow => format!(
+                "\
+memory_grow:
+	A => B
+	$ => A :MLOAD({MEMORY_PAGES})
+	A => E
+	$ => B :ADD
+	$ => A :MLOAD({MEMORY_MAX_PAGES})
+	$ => C :LT
+	C :JMPNZ(memory_grow_fail)
+	B :MSTORE({MEMORY_PAGES})
+	B => A
+	65536 => B
+	0 => C, D
+	${{A * B}} => A :ARITH
+	A :MSTORE({MEMORY_BYTES})
+	E => A
+	:RETURN
+memory_grow_fail:
+	0xFFFFFFFF => A
+	:RETURN
+"
+            ),
+            Routine::BoundsCheck => format!(
+                "\
+mem_bounds_check:
+	E => A
+	C => B
+	$ => B :ADD
+	$ => A :MLOAD({MEMORY_BYTES})
+	$ => A :LT
+	A :JMPNZ(trap)
+	:RETURN
+"
+            ),
+            // A word of the byte is the byte times the word with 1 in each byte.
+            Routine::MemoryFill => format!(
+                "\
+mem_fill:
+	RR :MSTORE(bulk_rr)
+	E :MSTORE(bulk_dest)
+	C :MSTORE(bulk_len)
+	D => A
+	0xFF => B
+	$ => A :AND
+	A :MSTORE(bulk_value)
+mem_fill_loop:
+	$ => A :MLOAD(bulk_len)
+	A :JMPZ(mem_fill_done)
+	$ => E :MLOAD(bulk_dest)
+	32 => B
+	$ => B :LT
+	B :JMPNZ(mem_fill_byte)
+	E => A
+	31 => B
+	$ => B :AND
+	B :JMPNZ(mem_fill_byte)
+	:CALL(mem_locate)
+	$ => A :MLOAD(bulk_value)
+	0x{ones}n => B
+	0 => C, D
+	${{A * B}} => A :ARITH
+	A :MSTORE(E)
+	32 => C
+	:JMP(mem_fill_next)
+mem_fill_byte:
+	$ => A :MLOAD(bulk_value)
+	0xFF => D
+	:CALL(mem_store)
+	1 => C
+mem_fill_next:
+	$ => A :MLOAD(bulk_dest)
+	C => B
+	$ => A :ADD
+	A :MSTORE(bulk_dest)
+	$ => A :MLOAD(bulk_len)
+	$ => A :SUB
+	A :MSTORE(bulk_len)
+	:JMP(mem_fill_loop)
+mem_fill_done:
+	$ => RR :MLOAD(bulk_rr)
+	:RETURN
+",
+                ones = "01".repeat(32)
+            ),
+            // Copies backwards from the ends of the ranges if the destination
+            // is above the source, so that no byte is overwritten before it
+            // has been copied. The pointers then point right after the next
+            // bytes to copy, which are aligned if the pointers are.
+            Routine::MemoryCopy => "\
+mem_copy:
+	RR :MSTORE(bulk_rr)
+	E :MSTORE(bulk_dest)
+	D :MSTORE(bulk_src)
+	C :MSTORE(bulk_len)
+	D => A
+	E => B
+	$ => A :LT
+	A :MSTORE(bulk_backward)
+	A :JMPZ(mem_copy_loop)
+	C => A
+	D => B
+	$ => B :ADD
+	B :MSTORE(bulk_src)
+	E => B
+	$ => B :ADD
+	B :MSTORE(bulk_dest)
+mem_copy_loop:
+	$ => A :MLOAD(bulk_len)
+	A :JMPZ(mem_copy_done)
+	1 => C
+	32 => B
+	$ => B :LT
+	B :JMPNZ(mem_copy_step)
+	$ => A :MLOAD(bulk_src)
+	$ => B :MLOAD(bulk_dest)
+	$ => A :OR
+	31 => B
+	$ => B :AND
+	B :JMPNZ(mem_copy_step)
+	32 => C
+mem_copy_step:
+	C :MSTORE(bulk_step)
+	C => B
+	$ => A :MLOAD(bulk_backward)
+	A :JMPZ(mem_copy_move)
+	$ => A :MLOAD(bulk_src)
+	$ => A :SUB
+	A :MSTORE(bulk_src)
+	$ => A :MLOAD(bulk_dest)
+	$ => A :SUB
+	A :MSTORE(bulk_dest)
+mem_copy_move:
+	$ => E :MLOAD(bulk_src)
+	C => A
+	1 => B
+	$ => A :EQ
+	A :JMPNZ(mem_copy_byte)
+	:CALL(mem_locate)
+	$ => A :MLOAD(E)
+	A :MSTORE(bulk_value)
+	$ => E :MLOAD(bulk_dest)
+	:CALL(mem_locate)
+	$ => A :MLOAD(bulk_value)
+	A :MSTORE(E)
+	:JMP(mem_copy_next)
+mem_copy_byte:
+	0xFF => D
+	:CALL(mem_load)
+	$ => E :MLOAD(bulk_dest)
+	0xFF => D
+	:CALL(mem_store)
+mem_copy_next:
+	$ => B :MLOAD(bulk_step)
+	$ => A :MLOAD(bulk_backward)
+	A :JMPNZ(mem_copy_count)
+	$ => A :MLOAD(bulk_src)
+	$ => A :ADD
+	A :MSTORE(bulk_src)
+	$ => A :MLOAD(bulk_dest)
+	$ => A :ADD
+	A :MSTORE(bulk_dest)
+mem_copy_count:
+	$ => A :MLOAD(bulk_len)
+	$ => A :SUB
+	A :MSTORE(bulk_len)
+	:JMP(mem_copy_loop)
+mem_copy_done:
+	$ => RR :MLOAD(bulk_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::Trap => "\
+trap:
+	0 => A
+	1 :ASSERT
+"
+            .to_string(),
+            Routine::DivRem => "\
+div_rem:
+	A => E
+	0 => A
+	$ => A :EQ
+	A :JMPNZ(trap)
+	${E / B} => A
+	${E % B} => C
+	0 => D
+	E :ARITH
+	A => D
+	C => A
+	$ => A :LT
+	1 :ASSERT
+	D => A
+	:RETURN
+"
+            .to_string(),
+            // Divides the absolute values and then fixes up the signs: the
+            // remainder takes the sign of the dividend, the quotient is
+            // negative if the signs of the operands differ.
+            Routine::DivRemSigned => "\
+div_rem_s:
+	RR :MSTORE(div_rr)
+	D :MSTORE(div_sign_bit)
+	B :MSTORE(div_rhs)
+	A :MSTORE(div_lhs)
+	:CALL(div_rem_s_abs)
+	A :MSTORE(div_abs_lhs)
+	$ => A :MLOAD(div_rhs)
+	:CALL(div_rem_s_abs)
+	A => B
+	$ => A :MLOAD(div_abs_lhs)
+	:CALL(div_rem)
+	A :MSTORE(div_quotient)
+	$ => A :MLOAD(div_lhs)
+	$ => B :MLOAD(div_sign_bit)
+	$ => D :LT
+	C => A
+	D :JMPNZ(div_rem_s_remainder)
+	:CALL(div_rem_s_negate)
+div_rem_s_remainder:
+	A :MSTORE(div_remainder)
+	$ => A :MLOAD(div_lhs)
+	$ => B :MLOAD(div_rhs)
+	$ => A :XOR
+	$ => B :MLOAD(div_sign_bit)
+	$ => D :LT
+	$ => A :MLOAD(div_quotient)
+	0 => E
+	D :JMPZ(div_rem_s_negative)
+	$ => E :EQ
+	:JMP(div_rem_s_done)
+div_rem_s_negative:
+	:CALL(div_rem_s_negate)
+div_rem_s_done:
+	$ => C :MLOAD(div_remainder)
+	$ => RR :MLOAD(div_rr)
+	:RETURN
+div_rem_s_abs:
+	$ => B :MLOAD(div_sign_bit)
+	$ => C :LT
+	C :JMPZ(div_rem_s_negate)
+	:RETURN
+div_rem_s_negate:
+	A => C
+	$ => A :MLOAD(div_sign_bit)
+	A => B
+	$ => A :ADD
+	1 => B
+	$ => D :SUB
+	C => B
+	$ => A :SUB
+	D => B
+	$ => A :AND
+	:RETURN
+"
+            .to_string(),
+            // A negative `A` is shifted as `!((!A) >> k)`, which rounds the
+            // same way as the arithmetic shift.
+            Routine::ShiftRightSigned => "\
+shr_s:
+	RR :MSTORE(shr_s_rr)
+	D :MSTORE(shr_s_mask)
+	B => E
+	C => B
+	$ => C :LT
+	C :MSTORE(shr_s_positive)
+	C :JMPNZ(shr_s_divide)
+	D => B
+	$ => A :XOR
+shr_s_divide:
+	E => B
+	:CALL(div_rem)
+	$ => C :MLOAD(shr_s_positive)
+	C :JMPNZ(shr_s_done)
+	$ => B :MLOAD(shr_s_mask)
+	$ => A :XOR
+shr_s_done:
+	$ => RR :MLOAD(shr_s_rr)
+	:RETURN
+"
+            .to_string(),
+            // `A * 2^k` splits at `bits` into the bits rotated out at the top
+            // and the shifted bits, which don't overlap.
+            Routine::RotateLeft => "\
+rotl:
+	RR :MSTORE(rotl_rr)
+	D => E
+	0 => C, D
+	${A * B} => A :ARITH
+	E => B
+	:CALL(div_rem)
+	C => B
+	$ => A :ADD
+	$ => RR :MLOAD(rotl_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::NextByte => "\
+next_byte:
+	A => E
+	${E / 256} => A
+	256 => B
+	${E % 256} => C
+	0 => D
+	E :ARITH
+	A => D
+	C => A
+	$ => A :LT
+	1 :ASSERT
+	C => E
+	D => A
+	:RETURN
+"
+            .to_string(),
+            // Scans the bytes from the lowest one up, so the result is set
+            // last by the highest byte that is not zero.
+            Routine::Clz => "\
+clz:
+	RR :MSTORE(bits_rr)
+	D :MSTORE(bits_result)
+	A => E
+	D => A
+	8 => B
+	$ => A :SUB
+	A :MSTORE(bits_offset)
+	E => A
+clz_loop:
+	:CALL(next_byte)
+	A :MSTORE(bits_value)
+	E :JMPZ(clz_next)
+	:CALL(clz8)
+	$ => A :MLOAD(bits_offset)
+	$ => A :ADD
+	A :MSTORE(bits_result)
+clz_next:
+	$ => A :MLOAD(bits_offset)
+	A :JMPZ(clz_done)
+	8 => B
+	$ => A :SUB
+	A :MSTORE(bits_offset)
+	$ => A :MLOAD(bits_value)
+	:JMP(clz_loop)
+clz_done:
+	$ => A :MLOAD(bits_result)
+	$ => RR :MLOAD(bits_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::Ctz => "\
+ctz:
+	RR :MSTORE(bits_rr)
+	D :MSTORE(bits_width)
+	0 :MSTORE(bits_offset)
+ctz_loop:
+	:CALL(next_byte)
+	E :JMPNZ(ctz_found)
+	A => E
+	$ => A :MLOAD(bits_offset)
+	8 => B
+	$ => A :ADD
+	A :MSTORE(bits_offset)
+	$ => B :MLOAD(bits_width)
+	$ => C :EQ
+	C :JMPNZ(ctz_done)
+	E => A
+	:JMP(ctz_loop)
+ctz_found:
+	:CALL(ctz8)
+	$ => A :MLOAD(bits_offset)
+	$ => A :ADD
+ctz_done:
+	$ => RR :MLOAD(bits_rr)
+	:RETURN
+"
+            .to_string(),
+            Routine::Popcnt => "\
+popcnt:
+	RR :MSTORE(bits_rr)
+	0 :MSTORE(bits_result)
+	D :MSTORE(bits_offset)
+popcnt_loop:
+	:CALL(next_byte)
+	A :MSTORE(bits_value)
+	:CALL(popcnt8)
+	$ => A :MLOAD(bits_result)
+	$ => A :ADD
+	A :MSTORE(bits_result)
+	$ => A :MLOAD(bits_offset)
+	8 => B
+	$ => A :SUB
+	A :MSTORE(bits_offset)
+	A => C
+	$ => A :MLOAD(bits_value)
+	C :JMPNZ(popcnt_loop)
+	$ => A :MLOAD(bits_result)
+	$ => RR :MLOAD(bits_rr)
+	:RETURN
+"
+            .to_string(),
+        }
+    }
+}
+
+/// Returns a jump table that maps the byte `E` to `B = f(E)`.
+fn byte_table(label: &str, f: impl Fn(u8) -> u32) -> String {
+    let mut code = format!("{label}:\n\t:JMP(@{label}_table + E)\n{label}_table:\n");
+    for byte in 0..=u8::MAX {
+        code += &format!("\t{} => B :RETURN\n", f(byte));
+    }
+    code
+}
+
+/// The routines and soft-float functions used by a program, and whether it
+/// uses indirect calls.
+#[derive(Default)]
+pub(super) struct Runtime {
+    routines: BTreeSet<Routine>,
+    soft_float: BTreeSet<String>,
+    call_indirect: bool,
+}
+
+impl Runtime {
+    /// Marks a routine and everything it calls as used.
+    pub(super) fn require(&mut self, routine: Routine) {
+        if self.routines.insert(routine) {
+            for dependency in routine.dependencies() {
+                self.require(*dependency);
+            }
+        }
+    }
+
+    /// Marks the soft-float function exported as `name` as used.
+    pub(super) fn require_soft_float(&mut self, name: &str) {
+        self.soft_float.insert(name.to_string());
+    }
+
+    /// Export names of the soft-float functions called by the program.
+    pub(super) fn soft_float_functions(&self) -> &BTreeSet<String> {
+        &self.soft_float
+    }
+
+    /// Marks the table and the dispatch of indirect calls as used.
+    pub(super) fn require_call_indirect(&mut self) {
+        self.call_indirect = true;
+    }
+
+    pub(super) fn call_indirect_required(&self) -> bool {
+        self.call_indirect
+    }
+
+    /// Returns the `VAR` declarations used by the routines.
+    pub(super) fn declarations(&self) -> String {
+        let variables: BTreeSet<_> = self
+            .routines
+            .iter()
+            .flat_map(|routine| routine.variables())
+            .collect();
+        variables
+            .into_iter()
+            .map(|variable| format!("VAR GLOBAL {variable}\n"))
+            .collect()
+    }
+
+    /// Returns the code of the routines.
+    pub(super) fn code(&self) -> String {
+        self.routines.iter().map(|routine| routine.code()).collect()
+    }
+}
+
+/// Initial contents of linear memory and of the passive data segments,
+/// packed into words the same way loads and stores see them.
+#[derive(Default)]
+pub(super) struct MemoryImage {
+    words: BTreeMap<u64, [u8; 32]>,
+}
+
+impl MemoryImage {
+    pub(super) fn write(&mut self, address: u64, bytes: &[u8]) {
+        for (index, byte) in bytes.iter().enumerate() {
+            let address = address + index as u64;
+            self.words.entry(address / 32).or_insert([0; 32])[(address % 32) as usize] = *byte;
+        }
+    }
+
+    /// Emits one store per word that is not zero, as memory starts zeroed.
+    pub(super) fn emit(&self, assembler: &mut ZkAssembler) {
+        for (word, bytes) in &self.words {
+            let Some(last) = bytes.iter().rposition(|byte| *byte != 0) else {
+                continue;
+            };
+            let value: String = bytes[..=last]
+                .iter()
+                .rev()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            assembler.set_const(Register::E, u64::from(LINEAR_MEMORY_BASE) + word);
+            assembler.memory_set_const(format!("0x{value}n"), Register::E);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Software floating point.
+//!
+//! zkASM has no floating point instructions, so float operations call the
+//! functions of `softfloat.wat`, a library that only uses integer
+//! instructions. The library is compiled with the same code generator as the
+//! program, and the exported functions the program calls are included once,
+//! after the program's functions, together with the functions they call. Its
+//! exported functions are labeled `softfloat_` followed by their export name,
+//! e.g. `softfloat_f64_add`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use wasmparser::{ExternalKind, FunctionBody, Operator, Payload::*, StructuralType};
+
+use super::{compile_function, Config, ModuleContext, Runtime};
+
+const SOURCE: &str = include_str!("softfloat.wat");
+
+const FUNCTION_PREFIX: &str = "softfloat";
+
+/// Returns the label of the soft-float function exported as `name`.
+pub(super) fn label(name: &str) -> String {
+    format!("{FUNCTION_PREFIX}_{name}")
+}
+
+/// Compiles the soft-float functions required in `runtime` and the functions
+/// they call. Routines they use are added to `runtime`.
+pub(super) fn compile(
+    config: &Config,
+    runtime: &mut Runtime,
+    next_block_index: &mut u32,
+) -> Result<String> {
+    let module = wat::parse_str(SOURCE)?;
+    let mut module_context = ModuleContext::new(FUNCTION_PREFIX);
+    let mut exports = BTreeMap::new();
+    let mut bodies: Vec<FunctionBody> = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(&module) {
+        match payload? {
+            TypeSection(reader) => {
+                for ty in reader {
+                    match ty?.structural_type {
+                        StructuralType::Func(func) => module_context.types.push(func),
+                        _ => anyhow::bail!("Only function types are supported"),
+                    }
+                }
+            }
+            FunctionSection(reader) => {
+                for type_index in reader {
+                    module_context.functions.push(type_index?);
+                }
+            }
+            ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if let ExternalKind::Func = export.kind {
+                        exports.insert(export.name, export.index);
+                        module_context
+                            .function_names
+                            .insert(export.index, export.name.to_string());
+                    }
+                }
+            }
+            CodeSectionEntry(body) => bodies.push(body),
+            _ => {}
+        }
+    }
+
+    // The library has no imports, so function indices are body indices.
+    let mut pending = Vec::new();
+    for name in runtime.soft_float_functions() {
+        match exports.get(name.as_str()) {
+            Some(function_index) => pending.push(*function_index),
+            None => anyhow::bail!("Soft-float function {name} doesn't exist"),
+        }
+    }
+    let mut required = BTreeSet::new();
+    while let Some(function_index) = pending.pop() {
+        if required.insert(function_index) {
+            let mut operator_reader = bodies[function_index as usize].get_operators_reader()?;
+            while !operator_reader.eof() {
+                if let Operator::Call { function_index } = operator_reader.read()? {
+                    pending.push(function_index);
+                }
+            }
+        }
+    }
+
+    let mut program = String::new();
+    for function_index in required {
+        program += &compile_function(
+            bodies[function_index as usize].clone(),
+            config,
+            &module_context,
+            runtime,
+            function_index,
+            next_block_index,
+        )?;
+        program += "\n";
+    }
+    Ok(program)
+}
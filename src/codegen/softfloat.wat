@@ -0,0 +1,1075 @@
+;; Software floating point, compiled along with the programs that use floats.
+;;
+;; Floats are passed as their IEEE 754 bit patterns, `f32` in an `i32` and
+;; `f64` in an `i64`. Every operation rounds to nearest, ties to even. NaNs
+;; behave like on x86: an operation returns its first NaN operand with the
+;; quiet bit set, and invalid operations return the default NaN, which has the
+;; sign bit set.
+;;
+;; The `f64` operations follow Berkeley SoftFloat. A significand being rounded
+;; has its leading bit at bit 62 and 10 extra bits below the result, the
+;; lowest one sticky. Its exponent is one less than the biased exponent of the
+;; result, as packing adds the leading bit into the exponent field.
+;;
+;; `f32` operations are carried out on `f64`s. `f64` has more than twice the
+;; precision of `f32`, so rounding the exact `f64` result of an addition,
+;; multiplication, division or square root to `f32` gives the correctly
+;; rounded `f32` result.
+(module
+ ;; Returns `a >> dist`, with the lowest bit set if any set bit was shifted
+ ;; out. `dist` must not be zero.
+ (func $shift_right_jam (param $a i64) (param $dist i64) (result i64)
+  (if (result i64) (i64.lt_u (local.get $dist) (i64.const 63))
+   (then
+    (i64.or
+     (i64.shr_u (local.get $a) (local.get $dist))
+     (i64.extend_i32_u
+      (i64.ne
+       (i64.shl (local.get $a) (i64.sub (i64.const 0) (local.get $dist)))
+       (i64.const 0)))))
+   (else (i64.extend_i32_u (i64.ne (local.get $a) (i64.const 0))))))
+
+ (func $is_nan (param $a i64) (result i32)
+  (i64.gt_u
+   (i64.and (local.get $a) (i64.const 0x7FFFFFFFFFFFFFFF))
+   (i64.const 0x7FF0000000000000)))
+
+ (func $propagate_nan (param $a i64) (param $b i64) (result i64)
+  (i64.or
+   (select (local.get $a) (local.get $b) (call $is_nan (local.get $a)))
+   (i64.const 0x0008000000000000)))
+
+ ;; Returns infinity of the given sign, or the default NaN for an infinity
+ ;; times zero.
+ (func $infinity_or_nan (param $sign i64) (param $magnitude i64) (result i64)
+  (select
+   (i64.or (local.get $sign) (i64.const 0x7FF0000000000000))
+   (i64.const 0xFFF8000000000000)
+   (i64.ne (local.get $magnitude) (i64.const 0))))
+
+ ;; The exponent and the significand of a normalized subnormal.
+ (func $subnormal_exp (param $sig i64) (result i64)
+  (i64.sub (i64.const 12) (i64.clz (local.get $sig))))
+
+ (func $subnormal_sig (param $sig i64) (result i64)
+  (i64.shl (local.get $sig) (i64.sub (i64.clz (local.get $sig)) (i64.const 11))))
+
+ (func $round_pack (param $sign i64) (param $exp i64) (param $sig i64) (result i64)
+  (local $round_bits i64)
+  (local.set $round_bits (i64.and (local.get $sig) (i64.const 0x3FF)))
+  (if (i64.lt_s (local.get $exp) (i64.const 0))
+   (then
+    (local.set $sig
+     (call $shift_right_jam (local.get $sig) (i64.sub (i64.const 0) (local.get $exp))))
+    (local.set $exp (i64.const 0))
+    (local.set $round_bits (i64.and (local.get $sig) (i64.const 0x3FF)))))
+  (if (result i64)
+   (i32.or
+    (i64.gt_s (local.get $exp) (i64.const 0x7FD))
+    (i32.and
+     (i64.eq (local.get $exp) (i64.const 0x7FD))
+     (i64.ge_u
+      (i64.add (local.get $sig) (i64.const 0x200))
+      (i64.const 0x8000000000000000))))
+   (then (i64.or (local.get $sign) (i64.const 0x7FF0000000000000)))
+   (else
+    (local.set $sig
+     (i64.shr_u (i64.add (local.get $sig) (i64.const 0x200)) (i64.const 10)))
+    (if (i64.eq (local.get $round_bits) (i64.const 0x200))
+     (then (local.set $sig (i64.and (local.get $sig) (i64.const -2)))))
+    (if (i64.eqz (local.get $sig))
+     (then (local.set $exp (i64.const 0))))
+    (i64.add
+     (i64.or (local.get $sign) (i64.shl (local.get $exp) (i64.const 52)))
+     (local.get $sig)))))
+
+ ;; Like `round_pack`, for a significand with its leading bit anywhere below
+ ;; bit 63.
+ (func $norm_round_pack (param $sign i64) (param $exp i64) (param $sig i64) (result i64)
+  (local $shift i64)
+  (local.set $shift (i64.sub (i64.clz (local.get $sig)) (i64.const 1)))
+  (local.set $exp (i64.sub (local.get $exp) (local.get $shift)))
+  (if (result i64)
+   (i32.and
+    (i64.ge_u (local.get $shift) (i64.const 10))
+    (i64.lt_u (local.get $exp) (i64.const 0x7FD)))
+   (then
+    (i64.add
+     (i64.or
+      (local.get $sign)
+      (i64.shl
+       (select (local.get $exp) (i64.const 0) (i64.ne (local.get $sig) (i64.const 0)))
+       (i64.const 52)))
+     (i64.shl (local.get $sig) (i64.sub (local.get $shift) (i64.const 10)))))
+   (else
+    (call $round_pack
+     (local.get $sign)
+     (local.get $exp)
+     (i64.shl (local.get $sig) (local.get $shift))))))
+
+ ;; Adds the magnitudes of `a` and `b`, giving the result the sign `sign`.
+ (func $add_mags (param $a i64) (param $b i64) (param $sign i64) (result i64)
+  (local $exp_a i64) (local $sig_a i64) (local $exp_b i64) (local $sig_b i64)
+  (local $exp_diff i64) (local $exp i64) (local $sig i64)
+  (local.set $exp_a (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_a (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_b (i64.and (i64.shr_u (local.get $b) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_b (i64.and (local.get $b) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_diff (i64.sub (local.get $exp_a) (local.get $exp_b)))
+  (if (result i64) (i64.eqz (local.get $exp_diff))
+   (then
+    (if (result i64) (i64.eqz (local.get $exp_a))
+     (then (i64.add (local.get $a) (local.get $sig_b)))
+     (else
+      (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+       (then
+        (if (result i64) (i64.eqz (i64.or (local.get $sig_a) (local.get $sig_b)))
+         (then (local.get $a))
+         (else (call $propagate_nan (local.get $a) (local.get $b)))))
+       (else
+        (call $round_pack
+         (local.get $sign)
+         (local.get $exp_a)
+         (i64.shl
+          (i64.add
+           (i64.const 0x0020000000000000)
+           (i64.add (local.get $sig_a) (local.get $sig_b)))
+          (i64.const 9))))))))
+   (else
+    (local.set $sig_a (i64.shl (local.get $sig_a) (i64.const 9)))
+    (local.set $sig_b (i64.shl (local.get $sig_b) (i64.const 9)))
+    (if (result i64) (i64.lt_s (local.get $exp_diff) (i64.const 0))
+     (then
+      (if (result i64) (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+       (then
+        (if (result i64) (i64.eqz (local.get $sig_b))
+         (then (i64.or (local.get $sign) (i64.const 0x7FF0000000000000)))
+         (else (call $propagate_nan (local.get $a) (local.get $b)))))
+       (else
+        (local.set $sig_a
+         (select
+          (i64.add (local.get $sig_a) (i64.const 0x2000000000000000))
+          (i64.shl (local.get $sig_a) (i64.const 1))
+          (i64.ne (local.get $exp_a) (i64.const 0))))
+        (call $add_mags_finish
+         (local.get $sign)
+         (local.get $exp_b)
+         (call $shift_right_jam
+          (local.get $sig_a)
+          (i64.sub (i64.const 0) (local.get $exp_diff)))
+         (local.get $sig_b)))))
+     (else
+      (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+       (then
+        (if (result i64) (i64.eqz (local.get $sig_a))
+         (then (local.get $a))
+         (else (call $propagate_nan (local.get $a) (local.get $b)))))
+       (else
+        (local.set $sig_b
+         (select
+          (i64.add (local.get $sig_b) (i64.const 0x2000000000000000))
+          (i64.shl (local.get $sig_b) (i64.const 1))
+          (i64.ne (local.get $exp_b) (i64.const 0))))
+        (call $add_mags_finish
+         (local.get $sign)
+         (local.get $exp_a)
+         (local.get $sig_a)
+         (call $shift_right_jam (local.get $sig_b) (local.get $exp_diff))))))))))
+
+ (func $add_mags_finish (param $sign i64) (param $exp i64) (param $sig_a i64) (param $sig_b i64) (result i64)
+  (local $sig i64)
+  (local.set $sig
+   (i64.add
+    (i64.const 0x2000000000000000)
+    (i64.add (local.get $sig_a) (local.get $sig_b))))
+  (if (i64.lt_u (local.get $sig) (i64.const 0x4000000000000000))
+   (then
+    (local.set $exp (i64.sub (local.get $exp) (i64.const 1)))
+    (local.set $sig (i64.shl (local.get $sig) (i64.const 1)))))
+  (call $round_pack (local.get $sign) (local.get $exp) (local.get $sig)))
+
+ ;; Subtracts the magnitude of `b` from the one of `a`, giving the result the
+ ;; sign `sign` if it is positive.
+ (func $sub_mags (param $a i64) (param $b i64) (param $sign i64) (result i64)
+  (local $exp_a i64) (local $sig_a i64) (local $exp_b i64) (local $sig_b i64)
+  (local $exp_diff i64) (local $sig_diff i64) (local $shift i64) (local $exp i64)
+  (local.set $exp_a (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_a (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_b (i64.and (i64.shr_u (local.get $b) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_b (i64.and (local.get $b) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_diff (i64.sub (local.get $exp_a) (local.get $exp_b)))
+  (if (result i64) (i64.eqz (local.get $exp_diff))
+   (then
+    (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+     (then
+      (if (result i64) (i64.eqz (i64.or (local.get $sig_a) (local.get $sig_b)))
+       (then (i64.const 0xFFF8000000000000))
+       (else (call $propagate_nan (local.get $a) (local.get $b)))))
+     (else
+      (local.set $sig_diff (i64.sub (local.get $sig_a) (local.get $sig_b)))
+      (if (result i64) (i64.eqz (local.get $sig_diff))
+       (then (i64.const 0))
+       (else
+        (if (i64.ne (local.get $exp_a) (i64.const 0))
+         (then (local.set $exp_a (i64.sub (local.get $exp_a) (i64.const 1)))))
+        (if (i64.lt_s (local.get $sig_diff) (i64.const 0))
+         (then
+          (local.set $sign (i64.xor (local.get $sign) (i64.const 0x8000000000000000)))
+          (local.set $sig_diff (i64.sub (i64.const 0) (local.get $sig_diff)))))
+        (local.set $shift (i64.sub (i64.clz (local.get $sig_diff)) (i64.const 11)))
+        (local.set $exp (i64.sub (local.get $exp_a) (local.get $shift)))
+        (if (i64.lt_s (local.get $exp) (i64.const 0))
+         (then
+          (local.set $shift (local.get $exp_a))
+          (local.set $exp (i64.const 0))))
+        (i64.add
+         (i64.or (local.get $sign) (i64.shl (local.get $exp) (i64.const 52)))
+         (i64.shl (local.get $sig_diff) (local.get $shift))))))))
+   (else
+    (local.set $sig_a (i64.shl (local.get $sig_a) (i64.const 10)))
+    (local.set $sig_b (i64.shl (local.get $sig_b) (i64.const 10)))
+    (if (result i64) (i64.lt_s (local.get $exp_diff) (i64.const 0))
+     (then
+      (local.set $sign (i64.xor (local.get $sign) (i64.const 0x8000000000000000)))
+      (if (result i64) (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+       (then
+        (if (result i64) (i64.eqz (local.get $sig_b))
+         (then (i64.or (local.get $sign) (i64.const 0x7FF0000000000000)))
+         (else (call $propagate_nan (local.get $a) (local.get $b)))))
+       (else
+        (local.set $sig_a
+         (i64.add
+          (local.get $sig_a)
+          (select
+           (i64.const 0x4000000000000000)
+           (local.get $sig_a)
+           (i64.ne (local.get $exp_a) (i64.const 0)))))
+        (local.set $sig_a
+         (call $shift_right_jam
+          (local.get $sig_a)
+          (i64.sub (i64.const 0) (local.get $exp_diff))))
+        (call $norm_round_pack
+         (local.get $sign)
+         (i64.sub (local.get $exp_b) (i64.const 1))
+         (i64.sub
+          (i64.or (local.get $sig_b) (i64.const 0x4000000000000000))
+          (local.get $sig_a))))))
+     (else
+      (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+       (then
+        (if (result i64) (i64.eqz (local.get $sig_a))
+         (then (local.get $a))
+         (else (call $propagate_nan (local.get $a) (local.get $b)))))
+       (else
+        (local.set $sig_b
+         (i64.add
+          (local.get $sig_b)
+          (select
+           (i64.const 0x4000000000000000)
+           (local.get $sig_b)
+           (i64.ne (local.get $exp_b) (i64.const 0)))))
+        (local.set $sig_b (call $shift_right_jam (local.get $sig_b) (local.get $exp_diff)))
+        (call $norm_round_pack
+         (local.get $sign)
+         (i64.sub (local.get $exp_a) (i64.const 1))
+         (i64.sub
+          (i64.or (local.get $sig_a) (i64.const 0x4000000000000000))
+          (local.get $sig_b))))))))))
+
+ (func $f64_add (export "f64_add") (param $a i64) (param $b i64) (result i64)
+  (local $sign i64)
+  (local.set $sign (i64.and (local.get $a) (i64.const 0x8000000000000000)))
+  (if (result i64)
+   (i64.eq (local.get $sign) (i64.and (local.get $b) (i64.const 0x8000000000000000)))
+   (then (call $add_mags (local.get $a) (local.get $b) (local.get $sign)))
+   (else (call $sub_mags (local.get $a) (local.get $b) (local.get $sign)))))
+
+ (func $f64_sub (export "f64_sub") (param $a i64) (param $b i64) (result i64)
+  (local $sign i64)
+  (local.set $sign (i64.and (local.get $a) (i64.const 0x8000000000000000)))
+  (if (result i64)
+   (i64.eq (local.get $sign) (i64.and (local.get $b) (i64.const 0x8000000000000000)))
+   (then (call $sub_mags (local.get $a) (local.get $b) (local.get $sign)))
+   (else (call $add_mags (local.get $a) (local.get $b) (local.get $sign)))))
+
+ ;; Returns the upper half of the 128-bit product of `a` and `b`, with the
+ ;; lowest bit set if the lower half is not zero.
+ (func $mul_high_jam (param $a i64) (param $b i64) (result i64)
+  (local $a1 i64) (local $a0 i64) (local $b1 i64) (local $b0 i64)
+  (local $low i64) (local $mid1 i64) (local $mid i64) (local $high i64)
+  (local.set $a1 (i64.shr_u (local.get $a) (i64.const 32)))
+  (local.set $a0 (i64.and (local.get $a) (i64.const 0xFFFFFFFF)))
+  (local.set $b1 (i64.shr_u (local.get $b) (i64.const 32)))
+  (local.set $b0 (i64.and (local.get $b) (i64.const 0xFFFFFFFF)))
+  (local.set $low (i64.mul (local.get $a0) (local.get $b0)))
+  (local.set $mid1 (i64.mul (local.get $a1) (local.get $b0)))
+  (local.set $mid (i64.add (local.get $mid1) (i64.mul (local.get $a0) (local.get $b1))))
+  (local.set $high
+   (i64.add
+    (i64.add
+     (i64.mul (local.get $a1) (local.get $b1))
+     (i64.shl
+      (i64.extend_i32_u (i64.lt_u (local.get $mid) (local.get $mid1)))
+      (i64.const 32)))
+    (i64.shr_u (local.get $mid) (i64.const 32))))
+  (local.set $mid (i64.shl (local.get $mid) (i64.const 32)))
+  (local.set $low (i64.add (local.get $low) (local.get $mid)))
+  (local.set $high
+   (i64.add
+    (local.get $high)
+    (i64.extend_i32_u (i64.lt_u (local.get $low) (local.get $mid)))))
+  (i64.or
+   (local.get $high)
+   (i64.extend_i32_u (i64.ne (local.get $low) (i64.const 0)))))
+
+ (func $f64_mul (export "f64_mul") (param $a i64) (param $b i64) (result i64)
+  (local $sign i64) (local $exp_a i64) (local $sig_a i64) (local $exp_b i64) (local $sig_b i64)
+  (local $exp i64) (local $sig i64)
+  (local.set $sign
+   (i64.and (i64.xor (local.get $a) (local.get $b)) (i64.const 0x8000000000000000)))
+  (local.set $exp_a (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_a (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_b (i64.and (i64.shr_u (local.get $b) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_b (i64.and (local.get $b) (i64.const 0x000FFFFFFFFFFFFF)))
+  (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+   (then
+    (if (result i64)
+     (i32.or
+      (i64.ne (local.get $sig_a) (i64.const 0))
+      (i32.and
+       (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+       (i64.ne (local.get $sig_b) (i64.const 0))))
+     (then (call $propagate_nan (local.get $a) (local.get $b)))
+     (else
+      (call $infinity_or_nan
+       (local.get $sign)
+       (i64.or (local.get $exp_b) (local.get $sig_b))))))
+   (else
+    (if (result i64) (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+     (then
+      (if (result i64) (i64.ne (local.get $sig_b) (i64.const 0))
+       (then (call $propagate_nan (local.get $a) (local.get $b)))
+       (else
+        (call $infinity_or_nan
+         (local.get $sign)
+         (i64.or (local.get $exp_a) (local.get $sig_a))))))
+     (else
+      (if (result i64)
+       (i32.or
+        (i64.eqz (i64.or (local.get $exp_a) (local.get $sig_a)))
+        (i64.eqz (i64.or (local.get $exp_b) (local.get $sig_b))))
+       (then (local.get $sign))
+       (else
+        (if (i64.eqz (local.get $exp_a))
+         (then
+          (local.set $exp_a (call $subnormal_exp (local.get $sig_a)))
+          (local.set $sig_a (call $subnormal_sig (local.get $sig_a)))))
+        (if (i64.eqz (local.get $exp_b))
+         (then
+          (local.set $exp_b (call $subnormal_exp (local.get $sig_b)))
+          (local.set $sig_b (call $subnormal_sig (local.get $sig_b)))))
+        (local.set $exp
+         (i64.sub (i64.add (local.get $exp_a) (local.get $exp_b)) (i64.const 0x3FF)))
+        (local.set $sig
+         (call $mul_high_jam
+          (i64.shl
+           (i64.or (local.get $sig_a) (i64.const 0x0010000000000000))
+           (i64.const 10))
+          (i64.shl
+           (i64.or (local.get $sig_b) (i64.const 0x0010000000000000))
+           (i64.const 11))))
+        (if (i64.lt_u (local.get $sig) (i64.const 0x4000000000000000))
+         (then
+          (local.set $exp (i64.sub (local.get $exp) (i64.const 1)))
+          (local.set $sig (i64.shl (local.get $sig) (i64.const 1)))))
+        (call $round_pack (local.get $sign) (local.get $exp) (local.get $sig)))))))))
+
+ (func $f64_div (export "f64_div") (param $a i64) (param $b i64) (result i64)
+  (local $sign i64) (local $exp_a i64) (local $sig_a i64) (local $exp_b i64) (local $sig_b i64)
+  (local $exp i64) (local $quotient i64) (local $remainder i64) (local $step i64)
+  (local.set $sign
+   (i64.and (i64.xor (local.get $a) (local.get $b)) (i64.const 0x8000000000000000)))
+  (local.set $exp_a (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_a (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (local.set $exp_b (i64.and (i64.shr_u (local.get $b) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig_b (i64.and (local.get $b) (i64.const 0x000FFFFFFFFFFFFF)))
+  (if (result i64) (i64.eq (local.get $exp_a) (i64.const 0x7FF))
+   (then
+    (if (result i64)
+     (i32.or
+      (i64.ne (local.get $sig_a) (i64.const 0))
+      (i32.and
+       (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+       (i64.ne (local.get $sig_b) (i64.const 0))))
+     (then (call $propagate_nan (local.get $a) (local.get $b)))
+     (else
+      (select
+       (i64.const 0xFFF8000000000000)
+       (i64.or (local.get $sign) (i64.const 0x7FF0000000000000))
+       (i64.eq (local.get $exp_b) (i64.const 0x7FF))))))
+   (else
+    (if (result i64) (i64.eq (local.get $exp_b) (i64.const 0x7FF))
+     (then
+      (if (result i64) (i64.ne (local.get $sig_b) (i64.const 0))
+       (then (call $propagate_nan (local.get $a) (local.get $b)))
+       (else (local.get $sign))))
+     (else
+      (if (result i64) (i64.eqz (i64.or (local.get $exp_b) (local.get $sig_b)))
+       (then
+        (select
+         (i64.const 0xFFF8000000000000)
+         (i64.or (local.get $sign) (i64.const 0x7FF0000000000000))
+         (i64.eqz (i64.or (local.get $exp_a) (local.get $sig_a)))))
+       (else
+        (if (result i64) (i64.eqz (i64.or (local.get $exp_a) (local.get $sig_a)))
+         (then (local.get $sign))
+         (else
+          (if (i64.eqz (local.get $exp_a))
+           (then
+            (local.set $exp_a (call $subnormal_exp (local.get $sig_a)))
+            (local.set $sig_a (call $subnormal_sig (local.get $sig_a)))))
+          (if (i64.eqz (local.get $exp_b))
+           (then
+            (local.set $exp_b (call $subnormal_exp (local.get $sig_b)))
+            (local.set $sig_b (call $subnormal_sig (local.get $sig_b)))))
+          (local.set $sig_a (i64.or (local.get $sig_a) (i64.const 0x0010000000000000)))
+          (local.set $sig_b (i64.or (local.get $sig_b) (i64.const 0x0010000000000000)))
+          (local.set $exp
+           (i64.add (i64.sub (local.get $exp_a) (local.get $exp_b)) (i64.const 0x3FE)))
+          (if (i64.lt_u (local.get $sig_a) (local.get $sig_b))
+           (then
+            (local.set $exp (i64.sub (local.get $exp) (i64.const 1)))
+            (local.set $sig_a (i64.shl (local.get $sig_a) (i64.const 1)))))
+          ;; Long division, 10 quotient bits at a time and then the last 2,
+          ;; until the leading quotient bit reaches bit 62.
+          (local.set $quotient (i64.div_u (local.get $sig_a) (local.get $sig_b)))
+          (local.set $remainder (i64.rem_u (local.get $sig_a) (local.get $sig_b)))
+          (loop $digits
+           (local.set $remainder (i64.shl (local.get $remainder) (i64.const 10)))
+           (local.set $quotient
+            (i64.or
+             (i64.shl (local.get $quotient) (i64.const 10))
+             (i64.div_u (local.get $remainder) (local.get $sig_b))))
+           (local.set $remainder (i64.rem_u (local.get $remainder) (local.get $sig_b)))
+           (local.set $step (i64.add (local.get $step) (i64.const 1)))
+           (br_if $digits (i64.lt_u (local.get $step) (i64.const 6))))
+          (local.set $remainder (i64.shl (local.get $remainder) (i64.const 2)))
+          (local.set $quotient
+           (i64.or
+            (i64.shl (local.get $quotient) (i64.const 2))
+            (i64.div_u (local.get $remainder) (local.get $sig_b))))
+          (local.set $remainder (i64.rem_u (local.get $remainder) (local.get $sig_b)))
+          (call $round_pack
+           (local.get $sign)
+           (local.get $exp)
+           (i64.or
+            (local.get $quotient)
+            (i64.extend_i32_u (i64.ne (local.get $remainder) (i64.const 0))))))))))))))
+
+ (func $f64_sqrt (export "f64_sqrt") (param $a i64) (result i64)
+  (local $exp i64) (local $sig i64) (local $root i64) (local $remainder i64)
+  (local $trial i64) (local $step i64)
+  (local.set $exp (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (local.set $sig (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (if (result i64) (i64.eq (local.get $exp) (i64.const 0x7FF))
+   (then
+    (if (result i64) (i64.ne (local.get $sig) (i64.const 0))
+     (then (call $propagate_nan (local.get $a) (local.get $a)))
+     (else
+      (select
+       (i64.const 0xFFF8000000000000)
+       (local.get $a)
+       (i64.lt_s (local.get $a) (i64.const 0))))))
+   (else
+    (if (result i64) (i64.eqz (i64.or (local.get $exp) (local.get $sig)))
+     (then (local.get $a))
+     (else
+      (if (result i64) (i64.lt_s (local.get $a) (i64.const 0))
+       (then (i64.const 0xFFF8000000000000))
+       (else
+        (if (i64.eqz (local.get $exp))
+         (then
+          (local.set $exp (call $subnormal_exp (local.get $sig)))
+          (local.set $sig (call $subnormal_sig (local.get $sig)))))
+        (local.set $sig (i64.or (local.get $sig) (i64.const 0x0010000000000000)))
+        ;; Make the unbiased exponent even, so it can be halved exactly.
+        (local.set $exp (i64.sub (local.get $exp) (i64.const 0x3FF)))
+        (if (i64.ne (i64.and (local.get $exp) (i64.const 1)) (i64.const 0))
+         (then
+          (local.set $exp (i64.sub (local.get $exp) (i64.const 1)))
+          (local.set $sig (i64.shl (local.get $sig) (i64.const 1)))))
+        ;; Computes the 55 bit square root of `sig << 56` one bit at a time,
+        ;; taking two bits of the radicand per step.
+        (loop $bits
+         (local.set $remainder
+          (i64.or
+           (i64.shl (local.get $remainder) (i64.const 2))
+           (select
+            (i64.and
+             (i64.shr_u
+              (local.get $sig)
+              (i64.sub (i64.const 52) (i64.shl (local.get $step) (i64.const 1))))
+             (i64.const 3))
+            (i64.const 0)
+            (i64.lt_u (local.get $step) (i64.const 27)))))
+         (local.set $trial
+          (i64.or (i64.shl (local.get $root) (i64.const 2)) (i64.const 1)))
+         (local.set $root (i64.shl (local.get $root) (i64.const 1)))
+         (if (i64.ge_u (local.get $remainder) (local.get $trial))
+          (then
+           (local.set $remainder (i64.sub (local.get $remainder) (local.get $trial)))
+           (local.set $root (i64.or (local.get $root) (i64.const 1)))))
+         (local.set $step (i64.add (local.get $step) (i64.const 1)))
+         (br_if $bits (i64.lt_u (local.get $step) (i64.const 55))))
+        (call $round_pack
+         (i64.const 0)
+         (i64.add (i64.shr_s (local.get $exp) (i64.const 1)) (i64.const 0x3FE))
+         (i64.or
+          (i64.shl (local.get $root) (i64.const 8))
+          (i64.extend_i32_u (i64.ne (local.get $remainder) (i64.const 0))))))))))))
+
+ ;; Rounds to an integer: `mode` 0 rounds to nearest, 1 down, 2 up and 3
+ ;; towards zero. Rounding to nearest quiets NaNs, the other modes return
+ ;; them as they are.
+ (func $round_to_int (param $a i64) (param $mode i32) (result i64)
+  (local $exp i64) (local $last_bit i64) (local $round_mask i64) (local $z i64)
+  (local.set $exp (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF)))
+  (if (result i64) (i64.le_u (local.get $exp) (i64.const 0x3FE))
+   (then
+    (if (result i64)
+     (i64.eqz (i64.and (local.get $a) (i64.const 0x7FFFFFFFFFFFFFFF)))
+     (then (local.get $a))
+     (else
+      ;; The magnitude is below 1, so the result is 0 or 1 of the same sign.
+      (i64.or
+       (i64.and (local.get $a) (i64.const 0x8000000000000000))
+       (select
+        (i64.const 0x3FF0000000000000)
+        (i64.const 0)
+        (if (result i32) (i32.eqz (local.get $mode))
+         (then
+          (i32.and
+           (i64.eq (local.get $exp) (i64.const 0x3FE))
+           (i64.ne (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)) (i64.const 0))))
+         (else
+          (if (result i32) (i32.eq (local.get $mode) (i32.const 1))
+           (then (i64.lt_s (local.get $a) (i64.const 0)))
+           (else
+            (if (result i32) (i32.eq (local.get $mode) (i32.const 2))
+             (then (i64.ge_s (local.get $a) (i64.const 0)))
+             (else (i32.const 0))))))))))))
+   (else
+    (if (result i64) (i64.ge_u (local.get $exp) (i64.const 0x433))
+     (then
+      (select
+       (i64.or (local.get $a) (i64.const 0x0008000000000000))
+       (local.get $a)
+       (i32.and (i32.eqz (local.get $mode)) (call $is_nan (local.get $a)))))
+     (else
+      (local.set $last_bit
+       (i64.shl (i64.const 1) (i64.sub (i64.const 0x433) (local.get $exp))))
+      (local.set $round_mask (i64.sub (local.get $last_bit) (i64.const 1)))
+      (local.set $z (local.get $a))
+      (if (i32.eqz (local.get $mode))
+       (then
+        (local.set $z
+         (i64.add (local.get $z) (i64.shr_u (local.get $last_bit) (i64.const 1))))
+        (if (i64.eqz (i64.and (local.get $z) (local.get $round_mask)))
+         (then
+          (local.set $z
+           (i64.and (local.get $z) (i64.xor (local.get $last_bit) (i64.const -1)))))))
+       (else
+        (if
+         (i32.or
+          (i32.and
+           (i32.eq (local.get $mode) (i32.const 1))
+           (i64.lt_s (local.get $a) (i64.const 0)))
+          (i32.and
+           (i32.eq (local.get $mode) (i32.const 2))
+           (i64.ge_s (local.get $a) (i64.const 0))))
+         (then (local.set $z (i64.add (local.get $z) (local.get $round_mask)))))))
+      (i64.and (local.get $z) (i64.xor (local.get $round_mask) (i64.const -1))))))))
+
+ (func $f64_nearest (export "f64_nearest") (param $a i64) (result i64)
+  (call $round_to_int (local.get $a) (i32.const 0)))
+
+ (func $f64_floor (export "f64_floor") (param $a i64) (result i64)
+  (call $round_to_int (local.get $a) (i32.const 1)))
+
+ (func $f64_ceil (export "f64_ceil") (param $a i64) (result i64)
+  (call $round_to_int (local.get $a) (i32.const 2)))
+
+ (func $f64_trunc (export "f64_trunc") (param $a i64) (result i64)
+  (call $round_to_int (local.get $a) (i32.const 3)))
+
+ (func $f64_eq (export "f64_eq") (param $a i64) (param $b i64) (result i32)
+  (if (result i32)
+   (i32.or (call $is_nan (local.get $a)) (call $is_nan (local.get $b)))
+   (then (i32.const 0))
+   (else
+    (i32.or
+     (i64.eq (local.get $a) (local.get $b))
+     (i64.eqz
+      (i64.and
+       (i64.or (local.get $a) (local.get $b))
+       (i64.const 0x7FFFFFFFFFFFFFFF)))))))
+
+ (func $f64_ne (export "f64_ne") (param $a i64) (param $b i64) (result i32)
+  (i32.eqz (call $f64_eq (local.get $a) (local.get $b))))
+
+ (func $f64_lt (export "f64_lt") (param $a i64) (param $b i64) (result i32)
+  (if (result i32)
+   (i32.or (call $is_nan (local.get $a)) (call $is_nan (local.get $b)))
+   (then (i32.const 0))
+   (else
+    (if (result i32)
+     (i64.lt_s (i64.xor (local.get $a) (local.get $b)) (i64.const 0))
+     (then
+      ;; Signs differ: `a` is less if it is negative, unless both are zeros.
+      (i32.and
+       (i64.lt_s (local.get $a) (i64.const 0))
+       (i64.ne
+        (i64.and
+         (i64.or (local.get $a) (local.get $b))
+         (i64.const 0x7FFFFFFFFFFFFFFF))
+        (i64.const 0))))
+     (else
+      (if (result i32) (i64.lt_s (local.get $a) (i64.const 0))
+       (then (i64.gt_u (local.get $a) (local.get $b)))
+       (else (i64.lt_u (local.get $a) (local.get $b)))))))))
+
+ (func $f64_le (export "f64_le") (param $a i64) (param $b i64) (result i32)
+  (if (result i32)
+   (i32.or (call $is_nan (local.get $a)) (call $is_nan (local.get $b)))
+   (then (i32.const 0))
+   (else
+    (if (result i32)
+     (i64.lt_s (i64.xor (local.get $a) (local.get $b)) (i64.const 0))
+     (then
+      (i32.or
+       (i64.lt_s (local.get $a) (i64.const 0))
+       (i64.eqz
+        (i64.and
+         (i64.or (local.get $a) (local.get $b))
+         (i64.const 0x7FFFFFFFFFFFFFFF)))))
+     (else
+      (if (result i32) (i64.lt_s (local.get $a) (i64.const 0))
+       (then (i64.ge_u (local.get $a) (local.get $b)))
+       (else (i64.le_u (local.get $a) (local.get $b)))))))))
+
+ (func $f64_gt (export "f64_gt") (param $a i64) (param $b i64) (result i32)
+  (call $f64_lt (local.get $b) (local.get $a)))
+
+ (func $f64_ge (export "f64_ge") (param $a i64) (param $b i64) (result i32)
+  (call $f64_le (local.get $b) (local.get $a)))
+
+ ;; A NaN operand is returned quieted. Of two zeros the result is -0 if either
+ ;; of them is.
+ (func $f64_min (export "f64_min") (param $a i64) (param $b i64) (result i64)
+  (if (result i64) (i32.or (call $is_nan (local.get $a)) (call $is_nan (local.get $b)))
+   (then
+    (i64.or
+     (select (local.get $a) (local.get $b) (call $is_nan (local.get $a)))
+     (i64.const 0x0008000000000000)))
+   (else
+    (if (result i64) (call $f64_eq (local.get $a) (local.get $b))
+     (then (i64.or (local.get $a) (local.get $b)))
+     (else
+      (select
+       (local.get $a)
+       (local.get $b)
+       (call $f64_lt (local.get $a) (local.get $b))))))))
+
+ ;; Like `f64_min`, but returns +0 of two zeros unless both are -0.
+ (func $f64_max (export "f64_max") (param $a i64) (param $b i64) (result i64)
+  (if (result i64) (i32.or (call $is_nan (local.get $a)) (call $is_nan (local.get $b)))
+   (then
+    (i64.or
+     (select (local.get $a) (local.get $b) (call $is_nan (local.get $a)))
+     (i64.const 0x0008000000000000)))
+   (else
+    (if (result i64) (call $f64_eq (local.get $a) (local.get $b))
+     (then (i64.and (local.get $a) (local.get $b)))
+     (else
+      (select
+       (local.get $b)
+       (local.get $a)
+       (call $f64_lt (local.get $a) (local.get $b))))))))
+
+ ;; Converts an `f32` to the `f64` of the same value, quieting NaNs.
+ (func $promote (export "f64_promote_f32") (param $a i32) (result i64)
+  (local $sign i64) (local $exp i64) (local $sig i64)
+  (local.set $sign
+   (i64.shl
+    (i64.extend_i32_u (i32.shr_u (local.get $a) (i32.const 31)))
+    (i64.const 63)))
+  (local.set $exp
+   (i64.extend_i32_u (i32.and (i32.shr_u (local.get $a) (i32.const 23)) (i32.const 0xFF))))
+  (local.set $sig (i64.extend_i32_u (i32.and (local.get $a) (i32.const 0x7FFFFF))))
+  (if (result i64) (i64.eq (local.get $exp) (i64.const 0xFF))
+   (then
+    (i64.or
+     (i64.or (local.get $sign) (i64.const 0x7FF0000000000000))
+     (select
+      (i64.or (i64.shl (local.get $sig) (i64.const 29)) (i64.const 0x0008000000000000))
+      (i64.const 0)
+      (i64.ne (local.get $sig) (i64.const 0)))))
+   (else
+    (if (result i64) (i64.eqz (i64.or (local.get $exp) (local.get $sig)))
+     (then (local.get $sign))
+     (else
+      (if (i64.eqz (local.get $exp))
+       (then
+        ;; Normalize the subnormal, whose leading bit becomes the implicit one.
+        (local.set $exp (i64.sub (i64.const 41) (i64.clz (local.get $sig))))
+        (local.set $sig
+         (i64.and
+          (i64.shl (local.get $sig) (i64.sub (i64.clz (local.get $sig)) (i64.const 40)))
+          (i64.const 0x7FFFFF)))))
+      (i64.or
+       (local.get $sign)
+       (i64.or
+        (i64.shl (i64.add (local.get $exp) (i64.const 896)) (i64.const 52))
+        (i64.shl (local.get $sig) (i64.const 29)))))))))
+
+ ;; Like `round_pack` for `f32`: the leading bit is at bit 30 and the
+ ;; significand has 7 extra bits.
+ (func $round_pack_f32 (param $sign i32) (param $exp i32) (param $sig i32) (result i32)
+  (local $round_bits i32)
+  (local.set $round_bits (i32.and (local.get $sig) (i32.const 0x7F)))
+  (if (i32.lt_s (local.get $exp) (i32.const 0))
+   (then
+    (local.set $sig
+     (i32.wrap_i64
+      (call $shift_right_jam
+       (i64.extend_i32_u (local.get $sig))
+       (i64.extend_i32_u (i32.sub (i32.const 0) (local.get $exp))))))
+    (local.set $exp (i32.const 0))
+    (local.set $round_bits (i32.and (local.get $sig) (i32.const 0x7F)))))
+  (if (result i32)
+   (i32.or
+    (i32.gt_s (local.get $exp) (i32.const 0xFD))
+    (i32.and
+     (i32.eq (local.get $exp) (i32.const 0xFD))
+     (i32.ge_u
+      (i32.add (local.get $sig) (i32.const 0x40))
+      (i32.const 0x80000000))))
+   (then (i32.or (local.get $sign) (i32.const 0x7F800000)))
+   (else
+    (local.set $sig
+     (i32.shr_u (i32.add (local.get $sig) (i32.const 0x40)) (i32.const 7)))
+    (if (i32.eq (local.get $round_bits) (i32.const 0x40))
+     (then (local.set $sig (i32.and (local.get $sig) (i32.const -2)))))
+    (if (i32.eqz (local.get $sig))
+     (then (local.set $exp (i32.const 0))))
+    (i32.add
+     (i32.or (local.get $sign) (i32.shl (local.get $exp) (i32.const 23)))
+     (local.get $sig)))))
+
+ ;; Rounds an `f64` to the nearest `f32`, quieting NaNs.
+ (func $demote (export "f32_demote_f64") (param $a i64) (result i32)
+  (local $sign i32) (local $exp i32) (local $frac i64) (local $sig i32)
+  (local.set $sign
+   (i32.shl (i32.wrap_i64 (i64.shr_u (local.get $a) (i64.const 63))) (i32.const 31)))
+  (local.set $exp
+   (i32.wrap_i64 (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF))))
+  (local.set $frac (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF)))
+  (if (result i32) (i32.eq (local.get $exp) (i32.const 0x7FF))
+   (then
+    (i32.or
+     (i32.or (local.get $sign) (i32.const 0x7F800000))
+     (select
+      (i32.or
+       (i32.wrap_i64 (i64.shr_u (local.get $frac) (i64.const 29)))
+       (i32.const 0x400000))
+      (i32.const 0)
+      (i64.ne (local.get $frac) (i64.const 0)))))
+   (else
+    ;; Keep 30 bits of the fraction, the lowest one sticky.
+    (local.set $sig
+     (i32.wrap_i64
+      (i64.or
+       (i64.shr_u (local.get $frac) (i64.const 22))
+       (i64.extend_i32_u
+        (i64.ne (i64.and (local.get $frac) (i64.const 0x3FFFFF)) (i64.const 0))))))
+    (if (result i32) (i32.eqz (i32.or (local.get $exp) (local.get $sig)))
+     (then (local.get $sign))
+     (else
+      (call $round_pack_f32
+       (local.get $sign)
+       (i32.sub (local.get $exp) (i32.const 0x381))
+       (i32.or (local.get $sig) (i32.const 0x40000000))))))))
+
+ (func $f32_is_nan (param $a i32) (result i32)
+  (i32.gt_u (i32.and (local.get $a) (i32.const 0x7FFFFFFF)) (i32.const 0x7F800000)))
+
+ (func (export "f32_add") (param $a i32) (param $b i32) (result i32)
+  (call $demote
+   (call $f64_add (call $promote (local.get $a)) (call $promote (local.get $b)))))
+
+ (func (export "f32_sub") (param $a i32) (param $b i32) (result i32)
+  (call $demote
+   (call $f64_sub (call $promote (local.get $a)) (call $promote (local.get $b)))))
+
+ (func (export "f32_mul") (param $a i32) (param $b i32) (result i32)
+  (call $demote
+   (call $f64_mul (call $promote (local.get $a)) (call $promote (local.get $b)))))
+
+ (func (export "f32_div") (param $a i32) (param $b i32) (result i32)
+  (call $demote
+   (call $f64_div (call $promote (local.get $a)) (call $promote (local.get $b)))))
+
+ (func (export "f32_sqrt") (param $a i32) (result i32)
+  (call $demote (call $f64_sqrt (call $promote (local.get $a)))))
+
+ (func $f32_round_to_int (param $a i32) (param $mode i32) (result i32)
+  (if (result i32) (call $f32_is_nan (local.get $a))
+   (then
+    (select
+     (i32.or (local.get $a) (i32.const 0x400000))
+     (local.get $a)
+     (i32.eqz (local.get $mode))))
+   (else
+    (call $demote (call $round_to_int (call $promote (local.get $a)) (local.get $mode))))))
+
+ (func (export "f32_nearest") (param $a i32) (result i32)
+  (call $f32_round_to_int (local.get $a) (i32.const 0)))
+
+ (func (export "f32_floor") (param $a i32) (result i32)
+  (call $f32_round_to_int (local.get $a) (i32.const 1)))
+
+ (func (export "f32_ceil") (param $a i32) (result i32)
+  (call $f32_round_to_int (local.get $a) (i32.const 2)))
+
+ (func (export "f32_trunc") (param $a i32) (result i32)
+  (call $f32_round_to_int (local.get $a) (i32.const 3)))
+
+ (func (export "f32_eq") (param $a i32) (param $b i32) (result i32)
+  (call $f64_eq (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ (func (export "f32_ne") (param $a i32) (param $b i32) (result i32)
+  (call $f64_ne (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ (func $f32_lt (export "f32_lt") (param $a i32) (param $b i32) (result i32)
+  (call $f64_lt (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ (func (export "f32_gt") (param $a i32) (param $b i32) (result i32)
+  (call $f64_gt (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ (func (export "f32_le") (param $a i32) (param $b i32) (result i32)
+  (call $f64_le (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ (func (export "f32_ge") (param $a i32) (param $b i32) (result i32)
+  (call $f64_ge (call $promote (local.get $a)) (call $promote (local.get $b))))
+
+ ;; Like `f64_min` and `f64_max`, on the `f32` bits.
+ (func (export "f32_min") (param $a i32) (param $b i32) (result i32)
+  (if (result i32) (i32.or (call $f32_is_nan (local.get $a)) (call $f32_is_nan (local.get $b)))
+   (then
+    (i32.or
+     (select (local.get $a) (local.get $b) (call $f32_is_nan (local.get $a)))
+     (i32.const 0x00400000)))
+   (else
+    (if (result i32) (call $f32_lt (local.get $a) (local.get $b))
+     (then (local.get $a))
+     (else
+      (if (result i32) (call $f32_lt (local.get $b) (local.get $a))
+       (then (local.get $b))
+       (else (i32.or (local.get $a) (local.get $b)))))))))
+
+ (func (export "f32_max") (param $a i32) (param $b i32) (result i32)
+  (if (result i32) (i32.or (call $f32_is_nan (local.get $a)) (call $f32_is_nan (local.get $b)))
+   (then
+    (i32.or
+     (select (local.get $a) (local.get $b) (call $f32_is_nan (local.get $a)))
+     (i32.const 0x00400000)))
+   (else
+    (if (result i32) (call $f32_lt (local.get $a) (local.get $b))
+     (then (local.get $b))
+     (else
+      (if (result i32) (call $f32_lt (local.get $b) (local.get $a))
+       (then (local.get $a))
+       (else (i32.and (local.get $a) (local.get $b)))))))))
+ ;; Converts the integer `a` to the `f64` of sign `sign` that is nearest to it.
+ (func $u64_to_f64 (param $sign i64) (param $a i64) (result i64)
+  (if (result i64) (i64.eqz (local.get $a))
+   (then (i64.const 0))
+   (else
+    (if (result i64) (i64.lt_s (local.get $a) (i64.const 0))
+     (then
+      (call $round_pack
+       (local.get $sign)
+       (i64.const 0x43D)
+       (call $shift_right_jam (local.get $a) (i64.const 1))))
+     (else (call $norm_round_pack (local.get $sign) (i64.const 0x43C) (local.get $a)))))))
+
+ ;; Converts the integer `a` to the `f32` of sign `sign` that is nearest to it.
+ (func $u64_to_f32 (param $sign i32) (param $a i64) (result i32)
+  (local $shift i64)
+  (local.set $shift (i64.sub (i64.clz (local.get $a)) (i64.const 40)))
+  (if (result i32) (i64.ge_s (local.get $shift) (i64.const 0))
+   ;; Fits into the significand.
+   (then
+    (if (result i32) (i64.eqz (local.get $a))
+     (then (i32.const 0))
+     (else
+      (i32.add
+       (i32.or
+        (local.get $sign)
+        (i32.shl
+         (i32.wrap_i64 (i64.sub (i64.const 0x95) (local.get $shift)))
+         (i32.const 23)))
+       (i32.wrap_i64 (i64.shl (local.get $a) (local.get $shift)))))))
+   (else
+    (local.set $shift (i64.add (local.get $shift) (i64.const 7)))
+    (call $round_pack_f32
+     (local.get $sign)
+     (i32.wrap_i64 (i64.sub (i64.const 0x9C) (local.get $shift)))
+     (i32.wrap_i64
+      (if (result i64) (i64.lt_s (local.get $shift) (i64.const 0))
+       (then
+        (call $shift_right_jam
+         (local.get $a)
+         (i64.sub (i64.const 0) (local.get $shift))))
+       (else (i64.shl (local.get $a) (local.get $shift)))))))))
+
+ (func $abs_i64 (param $a i64) (result i64)
+  (select
+   (i64.sub (i64.const 0) (local.get $a))
+   (local.get $a)
+   (i64.lt_s (local.get $a) (i64.const 0))))
+
+ (func (export "f64_convert_i32_s") (param $a i32) (result i64)
+  (call $u64_to_f64
+   (i64.and (i64.extend_i32_s (local.get $a)) (i64.const 0x8000000000000000))
+   (call $abs_i64 (i64.extend_i32_s (local.get $a)))))
+
+ (func (export "f64_convert_i32_u") (param $a i32) (result i64)
+  (call $u64_to_f64 (i64.const 0) (i64.extend_i32_u (local.get $a))))
+
+ (func (export "f64_convert_i64_s") (param $a i64) (result i64)
+  (call $u64_to_f64
+   (i64.and (local.get $a) (i64.const 0x8000000000000000))
+   (call $abs_i64 (local.get $a))))
+
+ (func (export "f64_convert_i64_u") (param $a i64) (result i64)
+  (call $u64_to_f64 (i64.const 0) (local.get $a)))
+
+ (func (export "f32_convert_i32_s") (param $a i32) (result i32)
+  (call $u64_to_f32
+   (i32.and (local.get $a) (i32.const 0x80000000))
+   (call $abs_i64 (i64.extend_i32_s (local.get $a)))))
+
+ (func (export "f32_convert_i32_u") (param $a i32) (result i32)
+  (call $u64_to_f32 (i32.const 0) (i64.extend_i32_u (local.get $a))))
+
+ (func (export "f32_convert_i64_s") (param $a i64) (result i32)
+  (call $u64_to_f32
+   (i32.and
+    (i32.wrap_i64 (i64.shr_u (local.get $a) (i64.const 32)))
+    (i32.const 0x80000000))
+   (call $abs_i64 (local.get $a))))
+
+ (func (export "f32_convert_i64_u") (param $a i64) (result i32)
+  (call $u64_to_f32 (i32.const 0) (local.get $a)))
+
+ ;; Truncates `a` to a `bits` wide integer, signed if `signed` is set. If `a`
+ ;; is NaN or the integer is out of range, traps unless `saturate` is set. Then
+ ;; NaN becomes 0 and other values the integer of their sign that is nearest.
+ (func $trunc (param $a i64) (param $signed i32) (param $bits i64) (param $saturate i32) (result i64)
+  (local $exp i64) (local $shift i64) (local $magnitude i64) (local $limit i64)
+  (local.set $exp
+   (i64.sub
+    (i64.and (i64.shr_u (local.get $a) (i64.const 52)) (i64.const 0x7FF))
+    (i64.const 0x3FF)))
+  (if (result i64) (call $is_nan (local.get $a))
+   (then
+    (if (i32.eqz (local.get $saturate))
+     (then (unreachable)))
+    (i64.const 0))
+   (else
+    (if (result i64) (i64.lt_s (local.get $exp) (i64.const 0))
+     (then (i64.const 0))
+     (else
+      ;; The largest magnitude of an integer of the sign of `a`.
+      (local.set $limit
+       (if (result i64) (local.get $signed)
+        (then
+         (i64.sub
+          (i64.shl (i64.const 1) (i64.sub (local.get $bits) (i64.const 1)))
+          (i64.extend_i32_u (i64.ge_s (local.get $a) (i64.const 0)))))
+        (else
+         (select
+          (i64.const 0)
+          (i64.const -1)
+          (i64.lt_s (local.get $a) (i64.const 0))))))
+      ;; Only meaningful if the exponent is below `bits`.
+      (local.set $magnitude
+       (i64.or
+        (i64.and (local.get $a) (i64.const 0x000FFFFFFFFFFFFF))
+        (i64.const 0x0010000000000000)))
+      (local.set $shift (i64.sub (local.get $exp) (i64.const 52)))
+      (local.set $magnitude
+       (if (result i64) (i64.lt_s (local.get $shift) (i64.const 0))
+        (then (i64.shr_u (local.get $magnitude) (i64.sub (i64.const 0) (local.get $shift))))
+        (else (i64.shl (local.get $magnitude) (local.get $shift)))))
+      (if
+       (i32.or
+        (i64.ge_s (local.get $exp) (local.get $bits))
+        (i64.gt_u (local.get $magnitude) (local.get $limit)))
+       (then
+        (if (i32.eqz (local.get $saturate))
+         (then (unreachable)))
+        (local.set $magnitude (local.get $limit))))
+      (select
+       (i64.sub (i64.const 0) (local.get $magnitude))
+       (local.get $magnitude)
+       (i64.lt_s (local.get $a) (i64.const 0))))))))
+
+ (func (export "i32_trunc_f32_s") (param $a i32) (result i32)
+  (i32.wrap_i64 (call $trunc (call $promote (local.get $a)) (i32.const 1) (i64.const 32) (i32.const 0))))
+
+ (func (export "i32_trunc_f32_u") (param $a i32) (result i32)
+  (i32.wrap_i64 (call $trunc (call $promote (local.get $a)) (i32.const 0) (i64.const 32) (i32.const 0))))
+
+ (func (export "i32_trunc_f64_s") (param $a i64) (result i32)
+  (i32.wrap_i64 (call $trunc (local.get $a) (i32.const 1) (i64.const 32) (i32.const 0))))
+
+ (func (export "i32_trunc_f64_u") (param $a i64) (result i32)
+  (i32.wrap_i64 (call $trunc (local.get $a) (i32.const 0) (i64.const 32) (i32.const 0))))
+
+ (func (export "i64_trunc_f32_s") (param $a i32) (result i64)
+  (call $trunc (call $promote (local.get $a)) (i32.const 1) (i64.const 64) (i32.const 0)))
+
+ (func (export "i64_trunc_f32_u") (param $a i32) (result i64)
+  (call $trunc (call $promote (local.get $a)) (i32.const 0) (i64.const 64) (i32.const 0)))
+
+ (func (export "i64_trunc_f64_s") (param $a i64) (result i64)
+  (call $trunc (local.get $a) (i32.const 1) (i64.const 64) (i32.const 0)))
+
+ (func (export "i64_trunc_f64_u") (param $a i64) (result i64)
+  (call $trunc (local.get $a) (i32.const 0) (i64.const 64) (i32.const 0)))
+
+ (func (export "i32_trunc_sat_f32_s") (param $a i32) (result i32)
+  (i32.wrap_i64 (call $trunc (call $promote (local.get $a)) (i32.const 1) (i64.const 32) (i32.const 1))))
+
+ (func (export "i32_trunc_sat_f32_u") (param $a i32) (result i32)
+  (i32.wrap_i64 (call $trunc (call $promote (local.get $a)) (i32.const 0) (i64.const 32) (i32.const 1))))
+
+ (func (export "i32_trunc_sat_f64_s") (param $a i64) (result i32)
+  (i32.wrap_i64 (call $trunc (local.get $a) (i32.const 1) (i64.const 32) (i32.const 1))))
+
+ (func (export "i32_trunc_sat_f64_u") (param $a i64) (result i32)
+  (i32.wrap_i64 (call $trunc (local.get $a) (i32.const 0) (i64.const 32) (i32.const 1))))
+
+ (func (export "i64_trunc_sat_f32_s") (param $a i32) (result i64)
+  (call $trunc (call $promote (local.get $a)) (i32.const 1) (i64.const 64) (i32.const 1)))
+
+ (func (export "i64_trunc_sat_f32_u") (param $a i32) (result i64)
+  (call $trunc (call $promote (local.get $a)) (i32.const 0) (i64.const 64) (i32.const 1)))
+
+ (func (export "i64_trunc_sat_f64_s") (param $a i64) (result i64)
+  (call $trunc (local.get $a) (i32.const 1) (i64.const 64) (i32.const 1)))
+
+ (func (export "i64_trunc_sat_f64_u") (param $a i64) (result i64)
+  (call $trunc (local.get $a) (i32.const 0) (i64.const 64) (i32.const 1))))
@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use wasmi::{Caller, Engine, Func, Linker, Module, Store};
+    use wasmi::{core::ValueType, Caller, Engine, Func, Linker, Module, Store, Value};
     use zkwasm::codegen;
 
-    fn test_module(name: &str) {
-        let wat = wat::parse_file(format!("data/{name}.wat")).expect("Failed to parse WAT file");
+    /// Runs the start function of the module in wasmi.
+    fn run_in_wasmi(wat: &[u8]) -> Result<(), wasmi::Error> {
         let engine = Engine::default();
-        let module = Module::new(&engine, &wat[..]).unwrap();
+        let module = Module::new(&engine, wat).unwrap();
 
         type HostState = ();
         let mut store = Store::new(&engine, ());
@@ -19,17 +19,471 @@ mod tests {
 
         let mut linker = <Linker<HostState>>::new(&engine);
         linker.define("env", "assert_eq", host_assert).unwrap();
-        let _ = linker
+        linker
             .instantiate(&mut store, &module)
             .unwrap()
             .start(&mut store)
-            .unwrap();
+            .map(|_| ())
+    }
+
+    fn test_module(name: &str) {
+        let wat = wat::parse_file(format!("data/{name}.wat")).expect("Failed to parse WAT file");
+        run_in_wasmi(&wat).unwrap();
+
+        let program = codegen::parse(&wat).unwrap();
+        let expected = expect_test::expect_file![format!("../data/generated/{name}.zkasm")];
+        expected.assert_eq(&program);
+    }
+
+    /// Like `test_module`, but for modules whose start function traps.
+    fn test_trapping_module(name: &str) {
+        let wat = wat::parse_file(format!("data/{name}.wat")).expect("Failed to parse WAT file");
+        let error = run_in_wasmi(&wat).unwrap_err();
+        assert!(matches!(error, wasmi::Error::Trap(_)), "{error}");
 
         let program = codegen::parse(&wat).unwrap();
         let expected = expect_test::expect_file![format!("../data/generated/{name}.zkasm")];
         expected.assert_eq(&program);
     }
 
+    /// Random bit patterns that favor special values: NaNs, infinities,
+    /// zeros, subnormals and exponents near the edges of the range.
+    struct FloatBits(u64);
+
+    impl FloatBits {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn next(&mut self, bits: u32) -> u64 {
+            let (exponent_bits, mantissa_bits) = if bits == 32 { (8, 23) } else { (11, 52) };
+            let max_exponent = (1 << exponent_bits) - 1;
+            let x = self.next_u64();
+            let sign = (x >> 63) << (bits - 1);
+            let mantissa = x & ((1 << mantissa_bits) - 1);
+            let exponent = match self.next_u64() % 8 {
+                0 => max_exponent,
+                1 => 0,
+                2 => (max_exponent >> 1) + x % 66 - 1,
+                3 => 1 + x % 4,
+                4 => max_exponent - 1 - x % 4,
+                _ => (x >> mantissa_bits) % (max_exponent + 1),
+            };
+            sign | exponent << mantissa_bits | mantissa
+        }
+    }
+
+    /// A float result as far as WASM specifies it: a NaN result only has to
+    /// be canonical when every NaN operand is, and any quiet NaN otherwise.
+    #[derive(Debug, PartialEq)]
+    enum FloatResult {
+        Bits(u64),
+        CanonicalNan,
+        ArithmeticNan,
+    }
+
+    impl FloatResult {
+        fn new(bits: u32, value: u64, canonical_operands: bool) -> Self {
+            match (nan_payload(bits, value), canonical_operands) {
+                (Some(NanPayload::Canonical), true) => Self::CanonicalNan,
+                (Some(NanPayload::Canonical | NanPayload::Quiet), false) => Self::ArithmeticNan,
+                _ => Self::Bits(value),
+            }
+        }
+    }
+
+    #[derive(PartialEq)]
+    enum NanPayload {
+        Canonical,
+        Quiet,
+        Signaling,
+    }
+
+    fn nan_payload(bits: u32, value: u64) -> Option<NanPayload> {
+        let mantissa_bits = if bits == 32 { 23 } else { 52 };
+        let magnitude = value & ((1 << (bits - 1)) - 1);
+        let infinity = ((1 << (bits - 1 - mantissa_bits)) - 1) << mantissa_bits;
+        let quiet = 1 << (mantissa_bits - 1);
+        if magnitude <= infinity {
+            None
+        } else if magnitude == infinity | quiet {
+            Some(NanPayload::Canonical)
+        } else if magnitude & quiet != 0 {
+            Some(NanPayload::Quiet)
+        } else {
+            Some(NanPayload::Signaling)
+        }
+    }
+
+    fn f64_specials() -> Vec<u64> {
+        let mut values = vec![
+            0x7ff8000000000000,
+            0x7ff8000000000123,
+            0x7ff0000000000001,
+            0x7ff4000000000000,
+            0x0000000000000001,
+            0x000fffffffffffff,
+            0x0010000000000000,
+            0x3ff0000000000001,
+            0x7fefffffffffffff,
+        ];
+        let floats = [
+            0.0,
+            f64::INFINITY,
+            1.0,
+            0.5,
+            1.5,
+            2.5,
+            // Ties of additions to 1.
+            f64::EPSILON / 2.0,
+            f64::EPSILON * 1.5,
+            // Edges of the integer ranges.
+            2147483647.0,
+            2147483647.5,
+            2147483648.0,
+            2147483648.5,
+            2147483649.0,
+            4294967295.0,
+            4294967295.5,
+            4294967296.0,
+            9007199254740993.0,
+            9223372036854774784.0,
+            9223372036854775808.0,
+            18446744073709549568.0,
+            18446744073709551616.0,
+            0.9,
+        ];
+        values.extend(floats.iter().map(|float: &f64| float.to_bits()));
+        let negated: Vec<u64> = values.iter().map(|bits| bits ^ 1 << 63).collect();
+        values.extend(negated);
+        values
+    }
+
+    fn f32_specials() -> Vec<u64> {
+        let mut values = vec![
+            0x7fc00000, 0x7fc00123, 0x7f800001, 0x7fa00000, 0x00000001, 0x007fffff, 0x00800000,
+            0x3f800001, 0x7f7fffff,
+        ];
+        let floats = [
+            0.0,
+            f32::INFINITY,
+            1.0,
+            0.5,
+            1.5,
+            2.5,
+            f32::EPSILON / 2.0,
+            f32::EPSILON * 1.5,
+            16777217.0,
+            2147483648.0,
+            4294967296.0,
+            9223372036854775808.0,
+            18446744073709551616.0,
+            0.9,
+        ];
+        values.extend(floats.iter().map(|float: &f32| float.to_bits() as u64));
+        let negated: Vec<u64> = values.iter().map(|bits| bits ^ 1 << 31).collect();
+        values.extend(negated);
+        values
+    }
+
+    fn int_specials() -> Vec<u64> {
+        let mut values: Vec<u64> = vec![
+            0,
+            1,
+            0x7fffffff,
+            0x80000000,
+            0x80000001,
+            0xffffffff,
+            // Ties when converting to f32 and to f64.
+            (1 << 24) + 1,
+            (1 << 24) + 3,
+            (1 << 53) + 1,
+            (1 << 53) + 3,
+            0x7ffffffffffffe00,
+            0x7fffffffffffffff,
+            0x8000000000000000,
+            0x8000000000000400,
+            0x8000000000000401,
+            0xfffffffffffffc00,
+        ];
+        let negated: Vec<u64> = values.iter().map(|value| value.wrapping_neg()).collect();
+        values.extend(negated);
+        values
+    }
+
+    /// Runs every export of the soft-float library and the native WASM
+    /// instruction it implements in wasmi, and checks that they agree bit for
+    /// bit, including on which inputs they trap.
+    #[test]
+    fn soft_float_matches_wasmi() {
+        let soft_wasm =
+            wat::parse_file("src/codegen/softfloat.wat").expect("Failed to parse soft-float");
+        let engine = Engine::default();
+        let mut store = Store::new(&engine, ());
+        let linker = <Linker<()>>::new(&engine);
+        let soft_module = Module::new(&engine, &soft_wasm[..]).unwrap();
+        let soft = linker
+            .instantiate(&mut store, &soft_module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+
+        // Wraps the native instruction behind each export into a function with
+        // the same signature, which passes floats as their bit patterns.
+        let exports: Vec<String> = soft_module
+            .exports()
+            .map(|export| export.name().to_string())
+            .collect();
+        let int_type = |ty: &ValueType| match ty {
+            ValueType::I32 => 32,
+            ValueType::I64 => 64,
+            _ => panic!("Soft-float functions take integers"),
+        };
+        let float_result = |name: &str| {
+            name.starts_with('f')
+                && !["eq", "ne", "lt", "gt", "le", "ge"]
+                    .iter()
+                    .any(|op| name.ends_with(op))
+        };
+        let mut native_wat = String::from("(module\n");
+        for name in &exports {
+            let ty = soft.get_func(&store, name).unwrap().ty(&store);
+            let (result_bits, instruction) =
+                (int_type(&ty.results()[0]), name.replacen('_', ".", 1));
+            let float_operands = !name.contains("convert");
+            let mut params = String::new();
+            let mut operands = String::new();
+            for (index, param) in ty.params().iter().enumerate() {
+                let bits = int_type(param);
+                params += &format!(" (param i{bits})");
+                operands += &match float_operands {
+                    true => format!(" (f{bits}.reinterpret_i{bits} (local.get {index}))"),
+                    false => format!(" (local.get {index})"),
+                };
+            }
+            // wasmi takes the sign of the min or max of two zeros from Rust,
+            // which differs between builds, and doesn't quiet NaN operands, so
+            // those are built from other instructions.
+            let mut body = match name.rsplit('_').next() {
+                Some(op @ ("min" | "max")) => {
+                    let float = format!("f{result_bits}");
+                    let a = format!("({float}.reinterpret_i{result_bits} (local.get 0))");
+                    let b = format!("({float}.reinterpret_i{result_bits} (local.get 1))");
+                    let (zeros, order) = if op == "min" {
+                        ("or", "lt")
+                    } else {
+                        ("and", "gt")
+                    };
+                    format!(
+                        "(if (result {float}) (i32.or ({float}.ne {a} {a}) ({float}.ne {b} {b})) \
+                         (then ({float}.add {a} {b})) \
+                         (else (if (result {float}) ({float}.eq {a} {b}) \
+                         (then ({float}.reinterpret_i{result_bits} \
+                         (i{result_bits}.{zeros} (local.get 0) (local.get 1)))) \
+                         (else (select {a} {b} ({float}.{order} {a} {b}))))))"
+                    )
+                }
+                _ => format!("({instruction}{operands})"),
+            };
+            if float_result(name) {
+                body = format!("(i{result_bits}.reinterpret_f{result_bits} {body})");
+            }
+            native_wat +=
+                &format!(" (func (export \"{name}\"){params} (result i{result_bits}) {body})\n");
+        }
+        native_wat += ")";
+        let native_wasm = wat::parse_str(native_wat).unwrap();
+        let native_module = Module::new(&engine, &native_wasm[..]).unwrap();
+        let native = linker
+            .instantiate(&mut store, &native_module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+
+        let mut random = FloatBits(0x1234567887654321);
+        let mut mismatches = Vec::new();
+        for name in &exports {
+            let soft_function = soft.get_func(&store, name).unwrap();
+            let native_function = native.get_func(&store, name).unwrap();
+            let ty = soft_function.ty(&store);
+            let bits: Vec<u32> = ty.params().iter().map(int_type).collect();
+            let result_bits = int_type(&ty.results()[0]);
+            let float_operands = !name.contains("convert");
+            let specials = match (float_operands, bits[0]) {
+                (false, _) => int_specials(),
+                (true, 32) => f32_specials(),
+                (true, _) => f64_specials(),
+            };
+
+            let mut inputs: Vec<Vec<u64>> = Vec::new();
+            for &a in &specials {
+                match bits.len() {
+                    1 => inputs.push(vec![a]),
+                    _ => inputs.extend(specials.iter().map(|&b| vec![a, b])),
+                }
+            }
+            for _ in 0..1000 {
+                inputs.push(
+                    bits.iter()
+                        .map(|&bits| match float_operands {
+                            true => random.next(bits),
+                            false => random.next_u64() >> (random.next_u64() % 64),
+                        })
+                        .collect(),
+                );
+            }
+
+            for input in inputs {
+                let arguments: Vec<Value> = input
+                    .iter()
+                    .zip(&bits)
+                    .map(|(&value, &bits)| match bits {
+                        32 => Value::I32(value as u32 as i32),
+                        _ => Value::I64(value as i64),
+                    })
+                    .collect();
+                let mut call = |function: Func| {
+                    let mut result = [Value::I32(0)];
+                    function
+                        .call(&mut store, &arguments, &mut result)
+                        .ok()
+                        .map(|_| match result[0] {
+                            Value::I32(value) => value as u32 as u64,
+                            Value::I64(value) => value as u64,
+                            _ => unreachable!(),
+                        })
+                };
+                let (soft_result, native_result) = (call(soft_function), call(native_function));
+                let canonical_operands = !float_operands
+                    || input.iter().zip(&bits).all(|(&value, &bits)| {
+                        matches!(nan_payload(bits, value), None | Some(NanPayload::Canonical))
+                    });
+                let class = |result: Option<u64>| match float_result(name) {
+                    true => {
+                        result.map(|value| FloatResult::new(result_bits, value, canonical_operands))
+                    }
+                    false => result.map(FloatResult::Bits),
+                };
+                if class(soft_result) != class(native_result) {
+                    mismatches.push(format!(
+                        "{name}{input:x?}: {soft_result:x?} instead of {native_result:x?}"
+                    ));
+                }
+            }
+        }
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+
+    #[test]
+    fn imported_global() {
+        let wat = wat::parse_str(r#"(module (import "env" "g" (global i32)))"#).unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(error.to_string(), "Imported global env.g is not supported");
+    }
+
+    #[test]
+    fn imported_function() {
+        let wat = wat::parse_str(r#"(module (import "env" "f" (func)))"#).unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Imported function env.f is not supported"
+        );
+    }
+
+    #[test]
+    fn element_segment_out_of_bounds() {
+        let wat =
+            wat::parse_str("(module (table 1 funcref) (func $f) (elem (i32.const 1) $f))").unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Element segment is out of bounds of the table"
+        );
+    }
+
+    #[test]
+    fn table_operators() {
+        let wat = wat::parse_str(
+            "(module (table 1 funcref) (func $f) (elem func $f)
+              (func (table.init 0 (i32.const 0) (i32.const 0) (i32.const 1))))",
+        )
+        .unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(error.to_string(), "Table operators are not supported");
+    }
+
+    #[test]
+    fn unsupported_proposal() {
+        let wat = wat::parse_str("(module (func (result v128) (v128.const i64x2 0 0)))").unwrap();
+        assert!(codegen::parse(&wat).is_err());
+    }
+
+    #[test]
+    fn memory_above_max_pages() {
+        let wat = wat::parse_str("(module (memory 4))").unwrap();
+        let config = codegen::Config {
+            max_memory_pages: 2,
+            ..Default::default()
+        };
+        let error = codegen::parse_with_config(&wat, &config).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Memory needs 4 pages, but at most 2 are allowed"
+        );
+    }
+
+    #[test]
+    fn memory_above_addressable_words() {
+        let wat = wat::parse_str("(module (memory 29))").unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Memory needs 29 pages, but at most 28 are allowed"
+        );
+    }
+
+    #[test]
+    fn table_above_addressable_words() {
+        let wat = wat::parse_str("(module (table 2049 funcref))").unwrap();
+        let error = codegen::parse(&wat).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Table has 2049 entries, but at most 2048 are allowed"
+        );
+    }
+
+    #[test]
+    fn elide_constant_bounds_checks() {
+        let compile = |address: u32| {
+            let wat = wat::parse_str(format!(
+                "(module (memory 1) (func (result i32) (i32.load (i32.const {address}))))"
+            ))
+            .unwrap();
+            let config = codegen::Config {
+                elide_constant_bounds_checks: true,
+                ..Default::default()
+            };
+            codegen::parse_with_config(&wat, &config).unwrap()
+        };
+        assert!(!compile(0xfffc).contains(":CALL(mem_bounds_check)"));
+        assert!(compile(0xfffd).contains(":CALL(mem_bounds_check)"));
+    }
+
+    #[test]
+    fn soft_float_called_functions() {
+        let wat = wat::parse_str(
+            "(module (func (param f64 f64) (result f64) (f64.add (local.get 0) (local.get 1))))",
+        )
+        .unwrap();
+        let program = codegen::parse(&wat).unwrap();
+        assert!(program.contains("softfloat_f64_add:"));
+        assert!(!program.contains("softfloat_f64_mul:"));
+    }
+
     macro_rules! testcases {
         { $($name:ident,)* } => {
           $(
@@ -48,5 +502,56 @@ mod tests {
         counter,
         fibonacci,
         add_func,
+        if_else,
+        calls,
+        branch_targets,
+        branch_unwind,
+        br_table,
+        add_memory,
+        memory,
+        memory_narrow,
+        globals,
+        data_segments,
+        memory_grow,
+        memory_bounds,
+        i32_wrapping,
+        i32_comparisons,
+        fibonacci_recursive,
+        i32_mul_div,
+        i32_shifts,
+        bit_counting,
+        i64,
+        floats,
+        add_floats,
+        float_conversions,
+        trunc_sat,
+        sign_extension,
+        bulk_memory,
+        multi_value,
+        early_return,
+        call_indirect,
+    }
+
+    macro_rules! trapping_testcases {
+        { $($name:ident,)* } => {
+          $(
+            #[test]
+            fn $name() {
+                test_trapping_module(stringify!($name));
+            }
+           )*
+        };
+    }
+
+    trapping_testcases! {
+        memory_out_of_bounds,
+        memory_offset_overflow,
+        i32_div_by_zero,
+        i32_div_overflow,
+        trunc_nan,
+        trunc_out_of_range,
+        call_indirect_out_of_bounds,
+        call_indirect_null,
+        call_indirect_signature,
     }
 }